@@ -51,7 +51,7 @@ pub fn html(item: TokenStream) -> TokenStream {
 
     fn elements_to_token_stream(elements: &[Element]) -> TokenStream {
 
-        let elements: proc_macro2::TokenStream = elements_to_with_token_stream(&elements).into();
+        let elements: proc_macro2::TokenStream = elements_to_with_token_stream(elements, Namespace::Html).into();
 
         quote::quote!(
             ::write_html::tags::fragment(::write_html::Empty)
@@ -59,20 +59,20 @@ pub fn html(item: TokenStream) -> TokenStream {
         ).into()
     }
 
-    fn elements_to_with_token_stream(elements: &[Element]) -> TokenStream {
+    fn elements_to_with_token_stream(elements: &[Element], namespace: Namespace) -> TokenStream {
         let mut tokens = Vec::new();
         for element in elements {
-            let element: proc_macro2::TokenStream = element_to_token_stream(element).into();
+            let element: proc_macro2::TokenStream = element_to_token_stream(element, namespace).into();
             let new_tokens: TokenStream = quote::quote!(
                 .child(#element)
             ).into();
-            tokens.extend(new_tokens.into_iter());
+            tokens.extend(new_tokens);
         }
 
         tokens.into_iter().collect()
     }
 
-    fn element_to_token_stream(element: &Element) -> TokenStream {
+    fn element_to_token_stream(element: &Element, namespace: Namespace) -> TokenStream {
         let mut tokens = Vec::new();
 
         match element {
@@ -80,9 +80,23 @@ pub fn html(item: TokenStream) -> TokenStream {
                 tokens.extend(group.clone());
             }
             Element::Literal(literal) => {
+                let is_str = is_str_literal(literal);
                 let literal: proc_macro2::TokenStream = TokenStream::from(TokenTree::Literal(literal.clone())).into();
+                let new_tokens: TokenStream = if is_str {
+                    quote::quote!(
+                        ::write_html::HtmlTextStr(#literal)
+                    ).into()
+                } else {
+                    quote::quote!(
+                        ::write_html::Text(#literal)
+                    ).into()
+                };
+                tokens.extend(new_tokens);
+            }
+            Element::Display(stream) => {
+                let stream: proc_macro2::TokenStream = stream.clone().into();
                 let new_tokens: TokenStream = quote::quote!(
-                    ::write_html::HtmlTextStr(#literal)
+                    ::write_html::Text(#stream)
                 ).into();
                 tokens.extend(new_tokens);
             }
@@ -95,20 +109,38 @@ pub fn html(item: TokenStream) -> TokenStream {
                 tokens.push(TokenTree::Ident(Ident::new("tags", tag.identifier_span)));
                 tokens.push(TokenTree::Punct(proc_macro::Punct::new(':', proc_macro::Spacing::Joint)));
                 tokens.push(TokenTree::Punct(proc_macro::Punct::new(':', proc_macro::Spacing::Alone)));
-                if is_ident(&tag.identifier) {
-                    tokens.push(TokenTree::Ident(Ident::new(&tag.identifier, tag.identifier_span)));
+                if namespace == Namespace::Html && !tag.children.is_empty() && HTML_VOID_TAGS.contains(&tag.identifier.as_str()) {
+                    let message = format!("void element <{}> can't have children", tag.identifier);
+                    let span = proc_macro2::Span::from(tag.identifier_span);
+                    return quote::quote_spanned!(span=> ::core::compile_error!(#message)).into();
+                }
+                if let Some(module) = namespace.module_of(&tag.identifier, !tag.children.is_empty()) {
+                    if let Some(module) = module {
+                        tokens.push(TokenTree::Ident(Ident::new(module, tag.identifier_span)));
+                        tokens.push(TokenTree::Punct(proc_macro::Punct::new(':', proc_macro::Spacing::Joint)));
+                        tokens.push(TokenTree::Punct(proc_macro::Punct::new(':', proc_macro::Spacing::Alone)));
+                    }
+                    if is_keyword(&tag.identifier) {
+                        tokens.push(TokenTree::Ident(Ident::new_raw(&tag.identifier, tag.identifier_span)));
+                    } else {
+                        tokens.push(TokenTree::Ident(Ident::new(&tag.identifier, tag.identifier_span)));
+                    }
                     {
                         let args = "(::write_html::Empty, ::write_html::Empty)";
                         let token_stream: TokenStream = args.parse().unwrap();
-                        tokens.extend(token_stream.into_iter());
+                        tokens.extend(token_stream);
                     }
                 } else {
+                    // unknown tags, like custom elements
                     tokens.push(TokenTree::Ident(Ident::new("tag", tag.identifier_span)));
                     {
                         let mut args = Vec::new();
                         args.push(TokenTree::Literal(Literal::string(&tag.identifier)));
-                        let token_stream: TokenStream = ", ::write_html::Empty, ::write_html::Empty, ::write_html::Compactability::No".parse().unwrap();
-                        args.extend(token_stream.into_iter());
+                        let token_stream: TokenStream = format!(
+                            ", ::write_html::Empty, ::write_html::Empty, {}",
+                            namespace.default_compactability(),
+                        ).parse().unwrap();
+                        args.extend(token_stream);
                         let group = Group::new(Delimiter::Parenthesis, args.into_iter().collect());
                         tokens.push(TokenTree::Group(group));
                     }
@@ -118,7 +150,7 @@ pub fn html(item: TokenStream) -> TokenStream {
                     tokens.push(TokenTree::Ident(Ident::new("attr", tag.identifier_span)));
                     {
                         let mut args = Vec::new();
-                        args.push(TokenTree::Literal(Literal::string(&key)));
+                        args.push(TokenTree::Literal(Literal::string(key)));
                         args.push(TokenTree::Punct(proc_macro::Punct::new(',', proc_macro::Spacing::Alone)));
                         if let Some(value) = value {
                             match value {
@@ -126,17 +158,25 @@ pub fn html(item: TokenStream) -> TokenStream {
                                     args.push(TokenTree::Literal(literal.clone()));
                                 }
                                 AttributeValue::Expression(stream) => {
-                                    args.extend(stream.clone().into_iter());
+                                    args.extend(stream.clone());
                                 }
                             }
                         } else {
-                            args.push(TokenTree::Literal(Literal::string("None")));
+                            // a valueless attribute, like `checked`
+                            args.push(TokenTree::Group(Group::new(Delimiter::Parenthesis, TokenStream::new())));
                         }
                         let group = Group::new(Delimiter::Parenthesis, args.into_iter().collect());
                         tokens.push(TokenTree::Group(group));
                     }
                 }
-                tokens.extend(elements_to_with_token_stream(&tag.children));
+                if let Some(condition) = &tag.condition {
+                    let condition: proc_macro2::TokenStream = condition.clone().into();
+                    let new_tokens: TokenStream = quote::quote!(
+                        .wrap_if(#condition)
+                    ).into();
+                    tokens.extend(new_tokens);
+                }
+                tokens.extend(elements_to_with_token_stream(&tag.children, namespace.of_children(&tag.identifier)));
             }
         }
 
@@ -145,30 +185,130 @@ pub fn html(item: TokenStream) -> TokenStream {
 
     //"42".parse().unwrap()
     //let result = element_to_token_stream(element);
-    let result = elements_to_token_stream(&elements);
 
-    result
+    elements_to_token_stream(&elements)
+}
+
+/// Tells whether the literal is a string literal (`"..."` or `r"..."`), as opposed to a number or a char.
+fn is_str_literal(literal: &Literal) -> bool {
+    let s = literal.to_string();
+    s.starts_with('"') || s.starts_with("r\"") || s.starts_with("r#")
 }
 
-fn is_ident(id: &str) -> bool {
-    if id.is_empty() {
-        return false;
+fn is_keyword(id: &str) -> bool {
+    id == "use"
+}
+
+/// Expands to one of the lists of tags known to [`html!`], as a `&[&str]`: `html`, `html_void`,
+/// `svg` or `math`.
+///
+/// It is only meant for the tests of `write_html`, which check the lists against `write_html::tags`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn known_tags(item: TokenStream) -> TokenStream {
+    let tags = match item.to_string().as_str() {
+        "html" => HTML_TAGS,
+        "html_void" => HTML_VOID_TAGS,
+        "svg" => SVG_TAGS,
+        "math" => MATH_TAGS,
+        list => panic!("Unknown list of tags: {}", list),
+    };
+    quote::quote!(&[#(#tags),*]).into()
+}
+
+/// The tags of `write_html::tags`, checked against it by the tests of `write_html`.
+const HTML_TAGS: &[&str] = &[
+    "a", "abbr", "address", "area", "article", "aside", "audio", "b", "base", "bdi", "bdo",
+    "blockquote", "body", "br", "button", "canvas", "caption", "cite", "code", "col", "colgroup",
+    "data", "datalist", "dd", "del", "details", "dfn", "dialog", "div", "dl", "dt", "em", "embed",
+    "fieldset", "figcaption", "figure", "footer", "form", "h1", "h2", "h3", "h4", "h5", "h6",
+    "head", "header", "hgroup", "hr", "html", "i", "iframe", "img", "input", "ins", "kbd", "label",
+    "legend", "li", "link", "main", "map", "mark", "math", "menu", "meta", "meter", "nav",
+    "noscript", "object", "ol", "optgroup", "option", "output", "p", "param", "picture", "pre",
+    "progress", "q", "rp", "rt", "ruby", "s", "samp", "script", "search", "section", "select",
+    "slot", "small", "source", "span", "strong", "style", "sub", "summary", "sup", "svg", "table",
+    "tbody", "td", "template", "textarea", "tfoot", "th", "thead", "time", "title", "tr", "track",
+    "u", "ul", "var", "video", "wbr",
+];
+
+/// The void elements of `write_html::tags`, whose functions don't take children.
+const HTML_VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// The tags of `write_html::tags::svg`, checked against it by the tests of `write_html`.
+const SVG_TAGS: &[&str] = &[
+    "a", "animate", "animateMotion", "animateTransform", "circle", "clipPath", "defs", "desc",
+    "ellipse", "feBlend", "feColorMatrix", "feComponentTransfer", "feComposite", "feConvolveMatrix",
+    "feDiffuseLighting", "feDisplacementMap", "feDistantLight", "feDropShadow", "feFlood", "feFuncA",
+    "feFuncB", "feFuncG", "feFuncR", "feGaussianBlur", "feImage", "feMerge", "feMergeNode",
+    "feMorphology", "feOffset", "fePointLight", "feSpecularLighting", "feSpotLight", "feTile",
+    "feTurbulence", "filter", "foreignObject", "g", "image", "line", "linearGradient", "marker",
+    "mask", "metadata", "mpath", "path", "pattern", "polygon", "polyline", "radialGradient", "rect",
+    "script", "set", "stop", "style", "svg", "switch", "symbol", "text", "textPath", "title", "tspan",
+    "use", "view",
+];
+
+/// The tags of `write_html::tags::math` (without the hyphenated ones), checked against it by the tests of `write_html`.
+const MATH_TAGS: &[&str] = &[
+    "annotation", "maction", "math", "merror", "mfrac", "mi", "mmultiscripts", "mn", "mo", "mover",
+    "mpadded", "mphantom", "mprescripts", "mroot", "mrow", "ms", "mspace", "msqrt", "mstyle", "msub",
+    "msubsup", "msup", "mtable", "mtd", "mtext", "mtr", "munder", "munderover", "semantics",
+];
+
+/// The namespace of the elements, which tells in which module of `write_html::tags` their functions are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Namespace {
+    Html,
+    Svg,
+    Math,
+    /// The content of an element unknown to `write_html::tags`, which may be an XML document
+    /// (like an RSS feed), where the HTML elements are looked up, but void elements can have children.
+    Xml,
+}
+
+impl Namespace {
+    /// Returns the submodule of `write_html::tags` with the function of `tag`, if any.
+    ///
+    /// Void elements with children (like `link` in an RSS feed) have no function, they can only
+    /// be written in XML (see `write_html::xml`), inside an element unknown to `write_html::tags`.
+    fn module_of(self, tag: &str, has_children: bool) -> Option<Option<&'static str>> {
+        match self {
+            Namespace::Html | Namespace::Xml if has_children && HTML_VOID_TAGS.contains(&tag) => None,
+            Namespace::Html | Namespace::Xml if HTML_TAGS.contains(&tag) => Some(None),
+            Namespace::Svg if SVG_TAGS.contains(&tag) => Some(Some("svg")),
+            Namespace::Math if MATH_TAGS.contains(&tag) => Some(Some("math")),
+            _ => None,
+        }
     }
-    if id.chars().next().unwrap().is_numeric() {
-        return false;
+
+    /// Returns the compactability of the unknown tags.
+    fn default_compactability(self) -> &'static str {
+        match self {
+            Namespace::Html | Namespace::Xml => "::write_html::Compactability::No",
+            Namespace::Svg | Namespace::Math => "::write_html::Compactability::Yes { final_slash: true }",
+        }
     }
-    for c in id.chars() {
-        if !c.is_alphanumeric() && c != '_' {
-            return false;
+
+    /// Returns the namespace of the children of `tag`.
+    fn of_children(self, tag: &str) -> Namespace {
+        match (self, tag) {
+            (Namespace::Html, "svg") => Namespace::Svg,
+            (Namespace::Html, "math") => Namespace::Math,
+            (Namespace::Svg, "foreignObject") => Namespace::Html,
+            (Namespace::Html, tag) if !HTML_TAGS.contains(&tag) => Namespace::Xml,
+            _ => self,
         }
     }
-    true
 }
 
 #[derive(Debug, Clone)]
 enum Element {
     Expression(TokenStream),
     Literal(Literal),
+    /// A literal that is not a single token, like `-1` or `true`, written through `Display`.
+    Display(TokenStream),
     Tag(Tag),
 }
 
@@ -200,6 +340,12 @@ fn parse_element(tokens: &[TokenTree]) -> Option<Parsed<Element>> {
     let first = tokens.first()?;
 
     match first {
+        TokenTree::Ident(ident) if matches!(ident.to_string().as_str(), "true" | "false") => {
+            Some(Parsed {
+                parsed: Element::Display(TokenStream::from(first.clone())),
+                next: 1,
+            })
+        }
         TokenTree::Ident(_ident) => {
             let tag = parse_tag_element(tokens)?;
             Some(Parsed {
@@ -213,6 +359,16 @@ fn parse_element(tokens: &[TokenTree]) -> Option<Parsed<Element>> {
                 next: 1,
             })
         }
+        TokenTree::Punct(punct) if punct.as_char() == '-' => {
+            // negative numbers are a minus sign followed by a literal
+            match tokens.get(1)? {
+                TokenTree::Literal(literal) if !is_str_literal(literal) => Some(Parsed {
+                    parsed: Element::Display(tokens[..2].iter().cloned().collect()),
+                    next: 2,
+                }),
+                _ => None,
+            }
+        }
         TokenTree::Punct(_punct) => {
             None
         }
@@ -243,6 +399,8 @@ struct Tag {
     identifier: String,
     identifier_span: proc_macro::Span,
     attributes: Vec<(String, Option<AttributeValue>)>,
+    /// The condition of `if (cond)`, under which the tag is written.
+    condition: Option<TokenStream>,
     children: Vec<Element>,
 }
 
@@ -257,6 +415,7 @@ fn parse_tag_element(tokens: &[TokenTree]) -> Option<ParsedTag> {
     let (identifier, next) = parse_html_identifier(tokens)?;
 
     let mut attributes = Vec::new();
+    let mut condition = None;
 
     let mut idx = next;
     loop {
@@ -270,12 +429,19 @@ fn parse_tag_element(tokens: &[TokenTree]) -> Option<ParsedTag> {
                     identifier,
                     identifier_span: tokens[0].span(), // TODO this is not correct
                     attributes,
+                    condition,
                     children: inner.parsed,
                 },
                 next: inner.next,
             })
         }
 
+        if let Some(cond) = parse_condition(&tokens[idx..]) {
+            condition = Some(cond);
+            idx += 2;
+            continue;
+        }
+
         if let Some((attribute, next2)) = parse_attribute(&tokens[idx..]) {
             attributes.push(attribute);
             idx += next2;
@@ -298,20 +464,20 @@ fn parse_tag_inner(tokens: &[TokenTree], start: usize) -> Option<Parsed<Vec<Elem
             if group.delimiter() == Delimiter::Brace {
                 let delimited = group.stream().into_iter().collect::<Vec<TokenTree>>();
                 let children = parse_mutliple_elements(&delimited)?;
-                return Some(Parsed {
+                Some(Parsed {
                     parsed: children,
                     next: start + 1,
-                });
+                })
             } else {
                 None
             }
         }
         TokenTree::Punct(punct) => {
             if punct.as_char() == ';' {
-                return Some(Parsed {
+                Some(Parsed {
                     parsed: Vec::new(),
                     next: start + 1,
-                });
+                })
             } else {
                 None
             }
@@ -320,6 +486,17 @@ fn parse_tag_inner(tokens: &[TokenTree], start: usize) -> Option<Parsed<Vec<Elem
     }
 }
 
+/// Parses `if (cond)`, returning `cond`.
+fn parse_condition(tokens: &[TokenTree]) -> Option<TokenStream> {
+    match tokens {
+        [TokenTree::Ident(ident), TokenTree::Group(group), ..]
+            if ident.to_string() == "if" && group.delimiter() == Delimiter::Parenthesis => {
+            Some(group.stream())
+        }
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone)]
 enum AttributeValue {
     Literal(Literal),
@@ -411,7 +588,7 @@ fn parse_html_identifier(tokens: &[TokenTree]) -> Option<(String, usize)> {
     //    }
     //};
 
-    // iterate over the tokens until we find a non-ident or non "-" token
+    // iterate over the tokens until we find a non-ident or non "-" or ":" token
     for token in tokens {
         match token {
             TokenTree::Ident(ident) => {
@@ -427,12 +604,12 @@ fn parse_html_identifier(tokens: &[TokenTree]) -> Option<(String, usize)> {
                 break;
             }
             TokenTree::Punct(punct) => {
-                if punct.as_char() == '-' {
+                if punct.as_char() == '-' || punct.as_char() == ':' {
                     let typ = Type::Punct;
                     if prev_type == Some(typ) {
                         break;
                     }
-                    identifier.push_str("-");
+                    identifier.push(punct.as_char());
                     prev_type = Some(typ);
                 } else {
                     break;
@@ -0,0 +1,63 @@
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use write_html::*;
+
+const LOREM: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+
+pub fn benchmark(c: &mut Criterion) {
+    c.bench_function("growing string", |b| b.iter(|| {
+        let mut page = String::new();
+        page.write_html(sample_html()).unwrap();
+        page
+    }));
+    c.bench_function("presized string", |b| b.iter(|| {
+        sample_html().to_html_string().unwrap()
+    }));
+}
+
+fn section() -> impl Html {
+    html!(
+        section .content {
+            h2 { "Section" }
+            p { (LOREM.as_html()) }
+            p { (LOREM.as_html()) }
+            p { (LOREM.as_html()) }
+            p { (LOREM.as_html()) }
+        }
+    )
+}
+
+fn sections() -> impl Html {
+    html!(
+        (section()) (section()) (section()) (section())
+        (section()) (section()) (section()) (section())
+        (section()) (section()) (section()) (section())
+        (section()) (section()) (section()) (section())
+    )
+}
+
+fn sample_html() -> impl Html {
+    html!(
+        (Doctype)
+        html lang="en" {
+            head {
+                (Head::new())
+                title { "Website!" }
+            }
+            body {
+                h1 { "It's a website!" }
+                (sections()) (sections()) (sections()) (sections())
+                (sections()) (sections()) (sections()) (sections())
+                (sections()) (sections()) (sections()) (sections())
+                (sections()) (sections()) (sections()) (sections())
+                footer {
+                    "Last modified"
+                    time { "2021-04-12" }
+                }
+            }
+        }
+    )
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
@@ -5,16 +5,31 @@ use std::fmt::Write;
 
 pub fn benchmark(c: &mut Criterion) {
     c.bench_function("sample html", |b| b.iter(|| sample_html().unwrap()));
+    c.bench_function("sample html minified", |b| b.iter(|| sample_html_minified().unwrap()));
+    c.bench_function("sample html builder", |b| b.iter(|| sample_html_builder().unwrap()));
 }
 
 fn sample_html() -> Result<String, Box<dyn std::error::Error>> {
-    
     let mut page = String::new();
     //page.reserve(1000);
+    write_sample_html(&mut page)?;
+    Ok(page)
+}
+
+/// Writes the sample page through a [`MinifyEnv`].
+///
+/// The sample page is 441 bytes, and 400 bytes once minified.
+fn sample_html_minified() -> Result<String, Box<dyn std::error::Error>> {
+    let mut env = MinifyEnv::new(String::new());
+    write_sample_html(&mut env)?;
+    Ok(env.finish()?)
+}
+
+fn write_sample_html(page: &mut impl HtmlEnv) -> Result<(), Error> {
 
     struct Links;
     impl Html for Links {
-        fn write_html(self, env: &mut impl HtmlEnv) -> std::fmt::Result {
+        fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
             struct Href(i32);
             impl AttributeValue for Href {
                 fn write_attribute_value(self, w: &mut impl Write) -> std::fmt::Result {
@@ -22,8 +37,9 @@ fn sample_html() -> Result<String, Box<dyn std::error::Error>> {
                 }
             }
             impl Html for Href {
-                fn write_html(self, env: &mut impl HtmlEnv) -> std::fmt::Result {
-                    env.write_fmt(format_args!("Page {}", self.0))
+                fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+                    env.write_fmt(format_args!("Page {}", self.0))?;
+                    Ok(())
                 }
             }
             for i in 0..2 {
@@ -40,7 +56,7 @@ fn sample_html() -> Result<String, Box<dyn std::error::Error>> {
         (Doctype)
         html lang="en" {
             head {
-                (DefaultMeta)
+                (Head::new())
                 title { "Website!" }
             }
             body {
@@ -68,6 +84,37 @@ fn sample_html() -> Result<String, Box<dyn std::error::Error>> {
         }
     ))?;
 
+    Ok(())
+}
+
+/// The same page as in `benches/html_builder.rs`, written with the builder methods of `InsideTagHtml`.
+fn sample_html_builder() -> Result<String, Box<dyn std::error::Error>> {
+    let mut page = String::new();
+    page.doctype()?;
+    let mut html = page.open_tag("html", Compactability::No)?.with_attr("lang", "en")?.inner_html()?;
+    let mut head = html.head()?.inner_html()?;
+    head.title()?.inner_html()?.text("Website!")?;
+    head.meta()?.with_attr("charset", "utf-8")?.close()?;
+    head.close()?;
+    let mut body = html.body()?.inner_html()?;
+    body.h1()?.inner_html()?.text("It's a website!")?;
+    let mut list = body.ul()?.inner_html()?;
+    for i in 0..2 {
+        list.li()?.inner_html()?
+            .a()?.with_attr("href", format!("/page_{}.html", i).as_str())?.inner_html()?
+            .text(format_args!("Page {}", i))?;
+    }
+    list.close()?;
+    let mut fig = body.figure()?.inner_html()?;
+    fig.img()?.with_attr("src", "img.jpg")?.with_attr("alt", "Awesome image")?.close()?;
+    fig.figcaption()?.inner_html()?.text("Awesome image")?;
+    fig.close()?;
+    let mut footer = body.footer()?.inner_html()?;
+    footer.text("Last modified")?;
+    footer.time()?.inner_html()?.text("2021-04-12")?;
+    footer.close()?;
+    body.close()?;
+    html.close()?;
     Ok(page)
 }
 
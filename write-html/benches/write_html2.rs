@@ -17,7 +17,7 @@ fn sample_html(page: &mut String) -> Result<(), Box<dyn std::error::Error>> {
         (Doctype)
         html lang="en" {
             head {
-                (DefaultMeta)
+                (Head::new())
                 title { "Website!" }
             }
             body {
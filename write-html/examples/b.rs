@@ -1,6 +1,6 @@
 use std::error::Error;
 
-use write_html::{html, Doctype, DefaultMeta, ToHtmlString};
+use write_html::{html, Doctype, Head, IoHtmlEnv};
 
 fn main() -> Result<(), Box<dyn Error>> {
     println!("Hello There!");
@@ -9,8 +9,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         (Doctype)
         html lang="en" {
             head {
-                (DefaultMeta)
-                title { "Website!" }
+                (Head::new().with_title("Website!"))
             }
             body {
                 h1 #some-id { "H1" }
@@ -25,14 +24,12 @@ fn main() -> Result<(), Box<dyn Error>> {
                 footer;
             }
         }
-    ).to_html_string()?;
+    );
 
-    {
-        use std::io::Write;
-        let file = "test.html";
-        let mut file = std::fs::File::create(file)?;
-        file.write_all(html_page.as_bytes())?;
-    }
+    let file = std::fs::File::create("test.html")?;
+    IoHtmlEnv::new(file)
+        .render(html_page)?
+        .flush()?;
 
     Ok(())
 }
\ No newline at end of file
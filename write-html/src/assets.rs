@@ -0,0 +1,292 @@
+use std::any::{Any, TypeId};
+use std::fmt::Write;
+
+use crate::{head::{push_script, push_unique, write_resources, Link}, Error, Html, HtmlEnv, Script, Serialization};
+
+
+/// The stylesheets, scripts and preloads needed by the components of a page.
+///
+/// A component declares its assets by writing an `Assets`, which writes nothing but adds them
+/// to the ones of the environment (see [`HtmlEnv::assets`]). They are then written where the
+/// page puts a [`HeadSlot`] and a [`BodyEndSlot`], when it is rendered with [`with_assets`].
+///
+/// Declaring the same stylesheet, script or preload twice adds it once.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// fn code_block(code: &str) -> impl Html + '_ {
+///     html!(
+///         (Assets::new().with_stylesheet("/highlight.css"))
+///         pre { code { (code.as_html_text()) } }
+///     )
+/// }
+///
+/// fn chart() -> impl Html {
+///     html!(
+///         (Assets::new()
+///             .with_preload("/chart-data.json", "fetch")
+///             .with_body_script(Script::new("/chart.js")))
+///         canvas class="chart" {}
+///     )
+/// }
+///
+/// let page = with_assets(|| html!(
+///     html {
+///         head { (HeadSlot) }
+///         body {
+///             (code_block("let x = 1;"))
+///             (chart())
+///             (code_block("x + 1"))
+///             (BodyEndSlot)
+///         }
+///     }
+/// ));
+/// assert_eq!(page.to_html_string().unwrap(), concat!(
+///     "<html><head>",
+///     "<link rel=\"stylesheet\" href=\"/highlight.css\">",
+///     "<link rel=\"preload\" href=\"/chart-data.json\" as=\"fetch\">",
+///     "</head><body>",
+///     "<pre><code>let x = 1;</code></pre>",
+///     "<canvas class=\"chart\"></canvas>",
+///     "<pre><code>x + 1</code></pre>",
+///     "<script src=\"/chart.js\"></script>",
+///     "</body></html>",
+/// ));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Assets {
+    scripts: Vec<Script>,
+    stylesheets: Vec<Link>,
+    preloads: Vec<Link>,
+    body_scripts: Vec<Script>,
+}
+
+impl Assets {
+    /// Creates an empty `Assets`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a stylesheet, written in the [`HeadSlot`].
+    pub fn with_stylesheet(mut self, href: impl Into<String>) -> Self {
+        push_unique(&mut self.stylesheets, Link::stylesheet(href));
+        self
+    }
+
+    /// Adds a script, written in the [`HeadSlot`] like the scripts of a [`Head`](crate::Head).
+    pub fn with_script(mut self, script: Script) -> Self {
+        push_script(&mut self.scripts, script);
+        self
+    }
+
+    /// Adds a `<link rel="preload">` of a resource of the given type, written in the [`HeadSlot`].
+    pub fn with_preload(mut self, href: impl Into<String>, as_type: impl Into<String>) -> Self {
+        push_unique(&mut self.preloads, Link::preload(href, as_type));
+        self
+    }
+
+    /// Adds a script written in the [`BodyEndSlot`], unless it is also in the [`HeadSlot`].
+    pub fn with_body_script(mut self, script: Script) -> Self {
+        push_script(&mut self.body_scripts, script);
+        self
+    }
+
+    /// Tells whether there are no assets.
+    pub fn is_empty(&self) -> bool {
+        self.scripts.is_empty() && self.stylesheets.is_empty() && self.preloads.is_empty() && self.body_scripts.is_empty()
+    }
+
+    /// Adds the assets of `other`, after the ones of `self`.
+    pub fn merge(&mut self, other: Assets) {
+        for script in other.scripts {
+            push_script(&mut self.scripts, script);
+        }
+        for link in other.stylesheets {
+            push_unique(&mut self.stylesheets, link);
+        }
+        for link in other.preloads {
+            push_unique(&mut self.preloads, link);
+        }
+        for script in other.body_scripts {
+            push_script(&mut self.body_scripts, script);
+        }
+    }
+}
+
+impl Html for Assets {
+    fn is_unit(&self) -> bool {
+        true
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(0))
+    }
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        if let Some(assets) = env.assets() {
+            assets.merge(self);
+        }
+        Ok(())
+    }
+}
+
+/// Writes the stylesheets, scripts and preloads of the [`Assets`] of the environment.
+///
+/// It goes in the `<head>` of the page, see [`with_assets`].
+pub struct HeadSlot;
+
+impl Html for HeadSlot {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        let Some(assets) = env.assets() else {
+            return Ok(());
+        };
+        let (scripts, stylesheets, preloads) = (assets.scripts.clone(), assets.stylesheets.clone(), assets.preloads.clone());
+        write_resources(env, scripts, &stylesheets, &preloads)
+    }
+}
+
+/// Writes the body scripts of the [`Assets`] of the environment.
+///
+/// It goes at the end of the `<body>` of the page, see [`with_assets`].
+pub struct BodyEndSlot;
+
+impl Html for BodyEndSlot {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        let Some(assets) = env.assets() else {
+            return Ok(());
+        };
+        let scripts: Vec<_> = assets.body_scripts.iter()
+            .filter(|script| !assets.scripts.iter().any(|s| s.src() == script.src()))
+            .cloned()
+            .collect();
+        for script in scripts {
+            env.write_html(script)?;
+        }
+        Ok(())
+    }
+}
+
+/// Html rendered in two passes, to write the [`Assets`] of its components in its slots.
+///
+/// See [`with_assets`].
+pub struct WithAssets<F>(pub F);
+
+impl<F, H> Html for WithAssets<F>
+where
+    F: Fn() -> H,
+    H: Html,
+{
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        // the first pass only collects the assets, its output is discarded
+        let mut collector = AssetsEnv { w: Discard { inner: &*env }, assets: Assets::new() };
+        collector.write_html((self.0)())?;
+        let assets = collector.assets;
+
+        let mut env = AssetsEnv { w: env, assets };
+        env.write_html((self.0)())?;
+        Ok(())
+    }
+}
+
+/// Renders the page returned by `f` with the [`Assets`] declared by its components written
+/// in its [`HeadSlot`] and [`BodyEndSlot`].
+///
+/// The page is rendered twice: once to collect the assets, which can be declared anywhere in
+/// the page (even after the slots), and once to write it. Therefore `f` should return the same
+/// page each time.
+///
+/// Without `with_assets`, the declared assets are ignored and the slots write nothing.
+///
+/// Both passes are rendered with the context of the environment (see [`with_context`](crate::with_context)),
+/// so the assets can depend on it.
+///
+/// See [`Assets`] for a complete example.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// struct Theme(&'static str);
+///
+/// fn themed() -> impl Html {
+///     from_context(|theme: Option<&Theme>| {
+///         Assets::new().with_stylesheet(format!("/{}.css", theme.map_or("light", |t| t.0)))
+///     })
+/// }
+///
+/// let page = with_context(
+///     ContextMap::new().with(Theme("dark")),
+///     with_assets(|| html!(head { (HeadSlot) } body { (themed()) })),
+/// );
+/// assert_eq!(
+///     page.to_html_string().unwrap(),
+///     "<head><link rel=\"stylesheet\" href=\"/dark.css\"></head><body></body>"
+/// );
+/// ```
+pub fn with_assets<F, H>(f: F) -> WithAssets<F>
+where
+    F: Fn() -> H,
+    H: Html,
+{
+    WithAssets(f)
+}
+
+/// An environment that collects the [`Assets`] of the components.
+struct AssetsEnv<W: HtmlEnv> {
+    w: W,
+    assets: Assets,
+}
+
+impl<W: HtmlEnv> Write for AssetsEnv<W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.w.write_str(s)
+    }
+}
+
+impl<W: HtmlEnv> HtmlEnv for AssetsEnv<W> {
+    fn serialization(&self) -> Serialization {
+        self.w.serialization()
+    }
+    fn before_open_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.w.before_open_tag(tag)
+    }
+    fn after_open_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.w.after_open_tag(tag)
+    }
+    fn before_close_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.w.before_close_tag(tag)
+    }
+    fn after_close_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.w.after_close_tag(tag)
+    }
+    fn assets(&mut self) -> Option<&mut Assets> {
+        Some(&mut self.assets)
+    }
+    fn implicit_close(&mut self, tag: &str) {
+        self.w.implicit_close(tag)
+    }
+    fn context_value(&self, type_id: TypeId) -> Option<&dyn Any> {
+        self.w.context_value(type_id)
+    }
+}
+
+/// An environment that discards what is written, with the serialization and the context of
+/// another environment.
+struct Discard<'e, E: HtmlEnv> {
+    inner: &'e E,
+}
+
+impl<'e, E: HtmlEnv> Write for Discard<'e, E> {
+    fn write_str(&mut self, _s: &str) -> std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<'e, E: HtmlEnv> HtmlEnv for Discard<'e, E> {
+    fn serialization(&self) -> Serialization {
+        self.inner.serialization()
+    }
+    fn context_value(&self, type_id: TypeId) -> Option<&dyn Any> {
+        self.inner.context_value(type_id)
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt::Write;
 
-use crate::{TagOpening, Empty, Sum};
+use crate::{TagOpening, Empty, Sum, HtmlEnv, Error};
 
 
 /// Represents a list of attributes.
@@ -9,11 +9,21 @@ pub trait Attributes {
     ///
     /// # Arguments
     /// * `w` - The tag opening to write to.
-    fn write_attributes<'a, 't, W: Write>(self, w: &mut TagOpening<'a, 't, W>) -> std::fmt::Result;
+    fn write_attributes<'a, 't, W: HtmlEnv>(self, w: &mut TagOpening<'a, 't, W>) -> Result<(), Error>;
+
+    /// Returns the bounds on the length of the written attributes, in bytes.
+    ///
+    /// See [`Html::size_hint`](crate::Html::size_hint).
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
 } // TODO TagOpening into trait and rename to TagOpening struct into something else
 
 impl<A: Attributes, B: Attributes> Attributes for Sum<A, B> {
-    fn write_attributes<'a, 't, W: Write>(self, w: &mut TagOpening<'a, 't, W>) -> std::fmt::Result {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        crate::add_size_hints(self.0.size_hint(), self.1.size_hint())
+    }
+    fn write_attributes<'a, 't, W: HtmlEnv>(self, w: &mut TagOpening<'a, 't, W>) -> Result<(), Error> {
         self.0.write_attributes(w)?;
         self.1.write_attributes(w)?;
         Ok(())
@@ -25,7 +35,7 @@ where
     Name: AttributeName,
     Value: AttributeValue,
 {
-    fn write_attributes<'a, 't, W: Write>(self, w: &mut TagOpening<'a, 't, W>) -> std::fmt::Result {
+    fn write_attributes<'a, 't, W: HtmlEnv>(self, w: &mut TagOpening<'a, 't, W>) -> Result<(), Error> {
         for (n, v) in self {
             w.attr(n, v)?;
         }
@@ -34,14 +44,43 @@ where
 }
 
 impl Attributes for Empty {
-    fn write_attributes<'a, 't, W: Write>(self, _w: &mut TagOpening<'a, 't, W>) -> std::fmt::Result {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(0))
+    }
+    fn write_attributes<'a, 't, W: HtmlEnv>(self, _w: &mut TagOpening<'a, 't, W>) -> Result<(), Error> {
         Ok(())
     }
 }
 
 
 /// Tells whether the given string is a valid attribute name.
+///
+/// The name can have a prefix, separated by a single colon, like the namespaced attributes
+/// of SVG (`xlink:href`, `xmlns:xlink` or `xml:lang`).
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// assert!(is_valid_attribute_name("data-id"));
+/// assert!(is_valid_attribute_name("xlink:href"));
+/// assert!(!is_valid_attribute_name("a:b:c"));
+/// assert!(!is_valid_attribute_name(":href"));
+///
+/// let s = tags::svg::svg(Empty, tags::svg::r#use(Empty, Empty).attr("xlink:href", "#icon"))
+///     .attr("xmlns:xlink", "http://www.w3.org/1999/xlink")
+///     .to_html_string().unwrap();
+/// assert_eq!(s, "<svg xmlns:xlink=\"http://www.w3.org/1999/xlink\"><use xlink:href=\"#icon\"/></svg>");
+/// ```
 pub fn is_valid_attribute_name(name: &str) -> bool {
+    match name.split_once(':') {
+        Some((prefix, local)) => is_valid_local_attribute_name(prefix) && is_valid_local_attribute_name(local),
+        None => is_valid_local_attribute_name(name),
+    }
+}
+
+/// Tells whether the given string is a valid attribute name, without prefix.
+fn is_valid_local_attribute_name(name: &str) -> bool {
     if name.is_empty() {
         return false;
     }
@@ -91,13 +130,13 @@ impl AttributeName for &&str {
         is_valid_attribute_name(self)
     }
     fn write_attribute_name(self, w: &mut impl Write) -> std::fmt::Result {
-        w.write_str(*self)
+        w.write_str(self)
     }
 }
 
 impl AttributeName for String {
     fn is_valid_attribute_name(&self) -> bool {
-        is_valid_attribute_name(&self)
+        is_valid_attribute_name(self)
     }
     fn write_attribute_name(self, w: &mut impl Write) -> std::fmt::Result {
         w.write_str(&self)
@@ -123,7 +162,34 @@ pub trait AttributeValue {
     ///
     /// # Arguments
     /// * `w` - The writer to write to.
-    fn write_attribute_value(self, w: &mut impl Write) -> std::fmt::Result;
+    fn write_attribute_value(self, w: &mut impl Write) -> std::fmt::Result
+    where
+        Self: Sized;
+}
+
+impl dyn AttributeValue {
+    /// Creates an [`AttributeValue`] from a closure, which streams the value into the writer.
+    ///
+    /// The written value is escaped as usual. As `AttributeValue` is a trait, it is called as
+    /// `<dyn AttributeValue>::from_fn`, or with its alias [`attribute_value_from_fn`].
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    /// use std::fmt::Write;
+    ///
+    /// let total = 3;
+    /// let s = tags::progress(Empty, Empty)
+    ///     .attr("max", <dyn AttributeValue>::from_fn(|w| write!(w, "{}", total)))
+    ///     .to_html_string().unwrap();
+    /// assert_eq!(s, "<progress max=\"3\"></progress>");
+    /// ```
+    pub fn from_fn<F>(f: F) -> AttributeValueFn<F>
+    where
+        F: FnOnce(&mut dyn Write) -> std::fmt::Result,
+    {
+        AttributeValueFn(f)
+    }
 }
 
 impl AttributeValue for &str {
@@ -140,7 +206,7 @@ impl AttributeValue for &&str {
         false
     }
     fn write_attribute_value(self, w: &mut impl Write) -> std::fmt::Result {
-        w.write_str(*self)
+        w.write_str(self)
     }
 }
 
@@ -171,11 +237,57 @@ impl AttributeValue for () {
     }
 }
 
-/*impl<F: FnOnce(&mut dyn Write) -> std::fmt::Result> AttributeValue for F {
-    fn is_unit(&self) -> bool {
-        false
-    }
+macro_rules! impl_attribute_value_display {
+    ($($t:ty)*) => {
+        $(
+            impl AttributeValue for $t {
+                fn write_attribute_value(self, w: &mut impl Write) -> std::fmt::Result {
+                    write!(w, "{}", self)
+                }
+            }
+        )*
+    };
+}
+
+impl_attribute_value_display!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize f32 f64 char);
+
+/// An attribute value written by a closure.
+///
+/// See [`<dyn AttributeValue>::from_fn`](AttributeValue#method.from_fn).
+pub struct AttributeValueFn<F>(pub F);
+
+impl<F: FnOnce(&mut dyn Write) -> std::fmt::Result> AttributeValue for AttributeValueFn<F> {
     fn write_attribute_value(self, w: &mut impl Write) -> std::fmt::Result {
-        self(w)
+        (self.0)(w)
     }
-}*/
\ No newline at end of file
+}
+
+/// Creates an [`AttributeValue`] from a closure, which streams the value into the writer.
+///
+/// This is an alias of [`<dyn AttributeValue>::from_fn`](AttributeValue#method.from_fn).
+///
+/// # Example
+/// ```
+/// use write_html::*;
+/// use std::fmt::Write;
+///
+/// let classes = ["a", "b"];
+/// let s = tags::div(Empty, Empty)
+///     .attr("class", attribute_value_from_fn(|w| {
+///         for (i, c) in classes.iter().enumerate() {
+///             if i > 0 {
+///                 w.write_str(" ")?;
+///             }
+///             w.write_str(c)?;
+///         }
+///         Ok(())
+///     }))
+///     .to_html_string().unwrap();
+/// assert_eq!(s, "<div class=\"a b\"></div>");
+/// ```
+pub fn attribute_value_from_fn<F>(f: F) -> AttributeValueFn<F>
+where
+    F: FnOnce(&mut dyn Write) -> std::fmt::Result,
+{
+    <dyn AttributeValue>::from_fn(f)
+}
@@ -0,0 +1,192 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::{Assets, Error, Html, HtmlEnv, Serialization};
+
+
+/// Values of distinct types, given to the components through the environment.
+///
+/// It holds the data of a request (like the locale, the current user or the current path) that
+/// the components read with [`HtmlEnv::context`], instead of receiving it in their
+/// constructors. See [`with_context`].
+#[derive(Default)]
+pub struct ContextMap {
+    values: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl ContextMap {
+    /// Creates an empty `ContextMap`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a value, replacing the one of the same type.
+    pub fn with<T: Any>(mut self, value: T) -> Self {
+        self.insert(value);
+        self
+    }
+
+    /// Inserts a value, and returns the one of the same type that it replaces.
+    pub fn insert<T: Any>(&mut self, value: T) -> Option<T> {
+        let old = self.values.insert(TypeId::of::<T>(), Box::new(value))?;
+        old.downcast().ok().map(|old| *old)
+    }
+
+    /// Returns the value of type `T`.
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.values.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    /// Tells whether there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Html written with values added to the context, see [`with_context`].
+pub struct WithContext<H> {
+    context: ContextMap,
+    html: H,
+}
+
+impl<H: Html> Html for WithContext<H> {
+    fn is_unit(&self) -> bool {
+        self.html.is_unit()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.html.size_hint()
+    }
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        let mut env = ContextEnv { w: env, context: self.context };
+        env.write_html(self.html)?;
+        Ok(())
+    }
+}
+
+/// Writes `html` with the values of `context` added to the context of the environment.
+///
+/// The values override the ones of the same type of the enclosing context, only inside `html`.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// struct Locale(&'static str);
+///
+/// fn greeting() -> impl Html {
+///     from_context(|locale: Option<&Locale>| match locale.map(|l| l.0) {
+///         Some("fr") => "Bonjour",
+///         _ => "Hello",
+///     }.as_html_text())
+/// }
+///
+/// let page = with_context(
+///     ContextMap::new().with(Locale("en")),
+///     html!(
+///         p { (greeting()) }
+///         (with_context(ContextMap::new().with(Locale("fr")), html!(p lang="fr" { (greeting()) })))
+///         p { (greeting()) }
+///     ),
+/// );
+/// assert_eq!(
+///     page.to_html_string().unwrap(),
+///     "<p>Hello</p><p lang=\"fr\">Bonjour</p><p>Hello</p>"
+/// );
+/// ```
+pub fn with_context<H: Html>(context: ContextMap, html: H) -> WithContext<H> {
+    WithContext { context, html }
+}
+
+/// Html created from a value of the context, see [`from_context`].
+pub struct FromContext<T, F>(F, std::marker::PhantomData<fn(&T)>);
+
+impl<T, F, H> Html for FromContext<T, F>
+where
+    T: Any,
+    F: FnOnce(Option<&T>) -> H,
+    H: Html,
+{
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        let html = (self.0)(env.context::<T>());
+        html.write_html(env)
+    }
+}
+
+/// Creates an [`Html`] from the value of type `T` of the context, given by the nearest
+/// enclosing [`with_context`] (or `None`).
+///
+/// It gives access to the context in `html!` expressions, which are evaluated before the
+/// environment is known. [`Html`] implementations can use [`HtmlEnv::context`] instead.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// struct CurrentPath(&'static str);
+///
+/// fn nav_link(href: &'static str, label: &'static str) -> impl Html {
+///     from_context(move |path: Option<&CurrentPath>| {
+///         let class = if path.is_some_and(|p| p.0 == href) { "active" } else { "" };
+///         tags::a([("href", href), ("class", class)], label.as_html_text())
+///     })
+/// }
+///
+/// let nav = with_context(
+///     ContextMap::new().with(CurrentPath("/blog")),
+///     html!(nav { (nav_link("/", "Home")) (nav_link("/blog", "Blog")) }),
+/// );
+/// assert_eq!(nav.to_html_string().unwrap(), concat!(
+///     "<nav><a href=\"/\" class=\"\">Home</a><a href=\"/blog\" class=\"active\">Blog</a></nav>",
+/// ));
+/// ```
+pub fn from_context<T, F, H>(f: F) -> FromContext<T, F>
+where
+    T: Any,
+    F: FnOnce(Option<&T>) -> H,
+    H: Html,
+{
+    FromContext(f, std::marker::PhantomData)
+}
+
+/// An environment that adds values to the context of another one.
+struct ContextEnv<W: HtmlEnv> {
+    w: W,
+    context: ContextMap,
+}
+
+impl<W: HtmlEnv> Write for ContextEnv<W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.w.write_str(s)
+    }
+}
+
+impl<W: HtmlEnv> HtmlEnv for ContextEnv<W> {
+    fn serialization(&self) -> Serialization {
+        self.w.serialization()
+    }
+    fn before_open_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.w.before_open_tag(tag)
+    }
+    fn after_open_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.w.after_open_tag(tag)
+    }
+    fn before_close_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.w.before_close_tag(tag)
+    }
+    fn after_close_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.w.after_close_tag(tag)
+    }
+    fn assets(&mut self) -> Option<&mut Assets> {
+        self.w.assets()
+    }
+    fn implicit_close(&mut self, tag: &str) {
+        self.w.implicit_close(tag)
+    }
+    fn context_value(&self, type_id: TypeId) -> Option<&dyn Any> {
+        match self.context.values.get(&type_id) {
+            Some(value) => Some(value.as_ref()),
+            None => self.w.context_value(type_id),
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::fmt::{Display, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+/// A date and time with a UTC offset, as written in feeds and sitemaps.
+///
+/// It is formatted with [`DateTime::rfc822`] (for RSS) or [`DateTime::rfc3339`] (for Atom and
+/// sitemaps), in the time zone of its offset.
+///
+/// # Example
+/// ```
+/// use write_html::DateTime;
+///
+/// let date = DateTime::from_ymd_hms(2024, 3, 1, 14, 30, 0).unwrap();
+/// assert_eq!(date.rfc822().to_string(), "Fri, 01 Mar 2024 14:30:00 +0000");
+/// assert_eq!(date.rfc3339().to_string(), "2024-03-01T14:30:00Z");
+///
+/// let date = date.with_offset(-5 * 60);
+/// assert_eq!(date.rfc822().to_string(), "Fri, 01 Mar 2024 09:30:00 -0500");
+/// assert_eq!(date.rfc3339().to_string(), "2024-03-01T09:30:00-05:00");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateTime {
+    /// The number of seconds since the Unix epoch.
+    timestamp: i64,
+    /// The offset from UTC, in minutes.
+    offset: i32,
+}
+
+impl DateTime {
+    /// Creates a UTC `DateTime` from a number of seconds since the Unix epoch.
+    pub fn from_timestamp(timestamp: i64) -> Self {
+        Self { timestamp, offset: 0 }
+    }
+
+    /// Creates a UTC `DateTime` from its date and time.
+    ///
+    /// Returns `None` if the date or the time is invalid (like February 30th or 24:00).
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::DateTime;
+    ///
+    /// assert_eq!(DateTime::from_ymd_hms(2000, 2, 29, 0, 0, 0).unwrap().timestamp(), 951782400);
+    /// assert_eq!(DateTime::from_ymd_hms(1969, 12, 31, 23, 59, 59).unwrap().timestamp(), -1);
+    /// assert!(DateTime::from_ymd_hms(2023, 2, 29, 0, 0, 0).is_none());
+    /// ```
+    pub fn from_ymd_hms(year: i64, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        if hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+        let days = days_from_civil(year, month, day);
+        let seconds = i64::from(hour * 3600 + minute * 60 + second);
+        Some(Self::from_timestamp(days * 86400 + seconds))
+    }
+
+    /// Returns the current date and time, in UTC.
+    pub fn now() -> Self {
+        SystemTime::now().into()
+    }
+
+    /// Returns the same instant, written with the given offset from UTC, in minutes.
+    pub fn with_offset(self, offset: i32) -> Self {
+        Self { offset, ..self }
+    }
+
+    /// Returns the number of seconds since the Unix epoch.
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Returns the offset from UTC, in minutes.
+    pub fn offset(&self) -> i32 {
+        self.offset
+    }
+
+    /// Formats the date as in [RFC 822](https://www.rfc-editor.org/rfc/rfc822#section-5), with
+    /// a four-digit year, like `Fri, 01 Mar 2024 14:30:00 +0000`.
+    pub fn rfc822(&self) -> impl Display {
+        let date = *self;
+        DisplayFn(move |f: &mut std::fmt::Formatter<'_>| {
+            let (days, seconds) = date.local();
+            let (year, month, day) = civil_from_days(days);
+            write!(
+                f, "{}, {:02} {} {:04} ",
+                WEEKDAYS[(days + 4).rem_euclid(7) as usize], day, MONTHS[month as usize - 1], year,
+            )?;
+            write_time(f, seconds)?;
+            let sign = if date.offset < 0 { '-' } else { '+' };
+            write!(f, " {}{:02}{:02}", sign, date.offset.abs() / 60, date.offset.abs() % 60)
+        })
+    }
+
+    /// Formats the date as in [RFC 3339](https://www.rfc-editor.org/rfc/rfc3339#section-5.6),
+    /// like `2024-03-01T14:30:00Z`.
+    pub fn rfc3339(&self) -> impl Display {
+        let date = *self;
+        DisplayFn(move |f: &mut std::fmt::Formatter<'_>| {
+            let (days, seconds) = date.local();
+            let (year, month, day) = civil_from_days(days);
+            write!(f, "{:04}-{:02}-{:02}T", year, month, day)?;
+            write_time(f, seconds)?;
+            if date.offset == 0 {
+                return f.write_char('Z');
+            }
+            let sign = if date.offset < 0 { '-' } else { '+' };
+            write!(f, "{}{:02}:{:02}", sign, date.offset.abs() / 60, date.offset.abs() % 60)
+        })
+    }
+
+    /// Returns the local day since the Unix epoch, and the second in that day.
+    fn local(&self) -> (i64, i64) {
+        let local = self.timestamp + i64::from(self.offset) * 60;
+        (local.div_euclid(86400), local.rem_euclid(86400))
+    }
+}
+
+impl From<SystemTime> for DateTime {
+    fn from(time: SystemTime) -> Self {
+        let timestamp = match time.duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_secs() as i64,
+            Err(e) => -(e.duration().as_secs_f64().ceil() as i64),
+        };
+        Self::from_timestamp(timestamp)
+    }
+}
+
+/// Implements [`Display`] with a closure.
+struct DisplayFn<F>(F);
+
+impl<F: Fn(&mut std::fmt::Formatter<'_>) -> std::fmt::Result> Display for DisplayFn<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self.0)(f)
+    }
+}
+
+/// Writes the time of the day `seconds`, as `hh:mm:ss`.
+fn write_time(f: &mut std::fmt::Formatter<'_>, seconds: i64) -> std::fmt::Result {
+    write!(f, "{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days since the Unix epoch of a date of the proleptic Gregorian calendar.
+///
+/// See [Howard Hinnant's algorithms](https://howardhinnant.github.io/date_algorithms.html#days_from_civil).
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Returns the year, month and day of a number of days since the Unix epoch, the inverse of
+/// [`days_from_civil`].
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
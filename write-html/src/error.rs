@@ -0,0 +1,147 @@
+use std::fmt::Display;
+
+
+/// An error that occurred while writing HTML.
+///
+/// Errors that happen inside a tag are wrapped in [`Error::InTag`], so that the error tells
+/// where it happened in the document.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let err = html!(
+///     html { body { table { tr {
+///         (TryHtml(Err::<Empty, _>("no data")))
+///     } } } }
+/// ).to_html_string().unwrap_err();
+///
+/// assert!(matches!(err.innermost(), Error::User(_)));
+/// assert_eq!(err.to_string(), "no data (in html > body > table > tr)");
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The environment failed to write, see [`std::fmt::Error`].
+    Fmt,
+    /// The underlying writer of an [`IoHtmlEnv`](crate::IoHtmlEnv) failed.
+    Io(std::io::Error),
+    /// An invalid tag name.
+    InvalidTagName(String),
+    /// An invalid attribute name.
+    InvalidAttributeName(String),
+    /// The written HTML is invalid.
+    Validation(String),
+    /// An error returned by the user, see [`TryHtml`](crate::TryHtml).
+    User(Box<dyn std::error::Error + Send + Sync>),
+    /// An error that happened inside a tag.
+    InTag {
+        /// The name of the tag.
+        tag: String,
+        /// The error.
+        source: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Creates an error returned by the user.
+    pub fn user(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Error::User(error.into())
+    }
+
+    /// Wraps `self` in an [`Error::InTag`].
+    pub fn in_tag(self, tag: &str) -> Self {
+        Error::InTag { tag: tag.to_owned(), source: Box::new(self) }
+    }
+
+    /// Returns the error without its [`Error::InTag`] context.
+    pub fn innermost(&self) -> &Error {
+        match self {
+            Error::InTag { source, .. } => source.innermost(),
+            e => e,
+        }
+    }
+
+    /// Returns the path of the tags in which the error happened, from the outermost.
+    pub fn path(&self) -> Vec<&str> {
+        let mut path = Vec::new();
+        let mut e = self;
+        while let Error::InTag { tag, source } = e {
+            path.push(tag.as_str());
+            e = source;
+        }
+        path
+    }
+
+    /// Replaces the innermost error with `f(innermost)`, keeping the context.
+    pub(crate) fn map_innermost(self, f: impl FnOnce(Error) -> Error) -> Error {
+        match self {
+            Error::InTag { tag, source } => Error::InTag { tag, source: Box::new(source.map_innermost(f)) },
+            e => f(e),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.innermost() {
+            Error::Fmt => write!(f, "failed to write HTML")?,
+            Error::Io(e) => write!(f, "{}", e)?,
+            Error::InvalidTagName(name) => write!(f, "invalid tag name: {:?}", name)?,
+            Error::InvalidAttributeName(name) => write!(f, "invalid attribute name: {:?}", name)?,
+            Error::Validation(msg) => write!(f, "{}", msg)?,
+            Error::User(e) => write!(f, "{}", e)?,
+            Error::InTag { .. } => unreachable!(),
+        }
+
+        let path = self.path();
+        if !path.is_empty() {
+            write!(f, " (in {})", path.join(" > "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self.innermost() {
+            Error::Io(e) => Some(e),
+            Error::User(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::fmt::Error> for Error {
+    fn from(_: std::fmt::Error) -> Self {
+        Error::Fmt
+    }
+}
+
+impl From<Error> for std::fmt::Error {
+    fn from(_: Error) -> Self {
+        std::fmt::Error
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<Error> for std::io::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Io(e) => e,
+            e => {
+                // keep the kind of the underlying error, if any
+                let kind = match e.innermost() {
+                    Error::Io(io) => io.kind(),
+                    _ => std::io::ErrorKind::Other,
+                };
+                std::io::Error::new(kind, e)
+            }
+        }
+    }
+}
@@ -1,9 +1,12 @@
 /*!
-Provides `StringEscaper` and `HtmlEscaper` to escape strings for use in string literals and HTML elements respectively.
+Provides `StringEscaper`, `HtmlEscaper`, `AttributeEscaper` and `XmlEscaper` to escape strings for
+use in string literals, HTML elements, HTML attribute values and XML documents respectively.
 */
 
 use std::fmt::Write;
 
+use crate::Serialization;
+
 
 
 /// Escapes a string for the inside of a string literal.
@@ -53,18 +56,26 @@ impl<'a, W: Write> Write for StringEscaper<'a, W> {
 
 /// Escapes a string for the inside of an HTML element.
 ///
+/// Newlines are written as `<br>`, or as `<br/>` in XHTML, and are kept in XML.
+///
 /// # Examples
 /// ```
 /// use std::fmt::Write;
-/// use write_html::escapes::HtmlEscaper;
+/// use write_html::{escapes::HtmlEscaper, Serialization};
 /// 
 /// let mut s = String::new();
 /// let mut escaper = HtmlEscaper::new(&mut s);
 /// escaper.write_str("<h1>H1</h1>").unwrap();
 /// assert_eq!(s, "&lt;h1&gt;H1&lt;/h1&gt;");
+///
+/// let mut s = String::new();
+/// let mut escaper = HtmlEscaper::with_serialization(&mut s, Serialization::Xml);
+/// escaper.write_str("a &\nb").unwrap();
+/// assert_eq!(s, "a &amp;\nb");
 /// ```
 pub struct HtmlEscaper<'a, W: Write> {
     w: &'a mut W,
+    serialization: Serialization,
 }
 
 impl<'a, W: Write> HtmlEscaper<'a, W> {
@@ -73,7 +84,16 @@ impl<'a, W: Write> HtmlEscaper<'a, W> {
     /// # Arguments
     /// * `w` - The `Write` to write to.
     pub fn new(w: &'a mut W) -> Self {
-        Self { w }
+        Self::with_serialization(w, Serialization::Html)
+    }
+
+    /// Creates a new `HtmlEscaper` that will write to `w` with the given serialization.
+    ///
+    /// # Arguments
+    /// * `w` - The `Write` to write to.
+    /// * `serialization` - The syntax of the document, which tells how newlines are written.
+    pub fn with_serialization(w: &'a mut W, serialization: Serialization) -> Self {
+        Self { w, serialization }
     }
 }
 
@@ -84,7 +104,11 @@ impl<'a, W: Write> Write for HtmlEscaper<'a, W> {
                 '<' => write!(self.w, "&lt;")?,
                 '>' => write!(self.w, "&gt;")?,
                 '&' => write!(self.w, "&amp;")?,
-                '\n' => write!(self.w, "<br>")?,
+                '\n' => match self.serialization {
+                    Serialization::Html => write!(self.w, "<br>")?,
+                    Serialization::Xhtml => write!(self.w, "<br/>")?,
+                    Serialization::Xml => self.w.write_char(c)?,
+                },
                 // TODO ...
                 _ => write!(self.w, "{}", c)?,
             };
@@ -92,4 +116,95 @@ impl<'a, W: Write> Write for HtmlEscaper<'a, W> {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Escapes a string for a quoted attribute value of an HTML document.
+///
+/// # Examples
+/// ```
+/// use std::fmt::Write;
+/// use write_html::escapes::AttributeEscaper;
+///
+/// let mut s = String::new();
+/// let mut escaper = AttributeEscaper::new(&mut s);
+/// escaper.write_str("Say \"hi\" & <wave>, it's").unwrap();
+/// assert_eq!(s, "Say &quot;hi&quot; &amp; &lt;wave&gt;, it's");
+/// ```
+pub struct AttributeEscaper<'a, W: Write> {
+    w: &'a mut W,
+}
+
+impl<'a, W: Write> AttributeEscaper<'a, W> {
+    /// Creates a new `AttributeEscaper` that will write to `w`.
+    ///
+    /// # Arguments
+    /// * `w` - The `Write` to write to.
+    pub fn new(w: &'a mut W) -> Self {
+        Self { w }
+    }
+}
+
+impl<'a, W: Write> Write for AttributeEscaper<'a, W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        let mut start = 0;
+        for (i, c) in s.char_indices() {
+            let escaped = match c {
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '&' => "&amp;",
+                '"' => "&quot;",
+                _ => continue,
+            };
+            self.w.write_str(&s[start..i])?;
+            self.w.write_str(escaped)?;
+            start = i + 1;
+        }
+        self.w.write_str(&s[start..])
+    }
+}
+
+/// Escapes a string for the text or the attribute values of an XML document.
+///
+/// # Examples
+/// ```
+/// use std::fmt::Write;
+/// use write_html::escapes::XmlEscaper;
+/// 
+/// let mut s = String::new();
+/// let mut escaper = XmlEscaper::new(&mut s);
+/// escaper.write_str("<a href=\"?a=1&b=2\">it's</a>").unwrap();
+/// assert_eq!(s, "&lt;a href=&quot;?a=1&amp;b=2&quot;&gt;it&apos;s&lt;/a&gt;");
+/// ```
+pub struct XmlEscaper<'a, W: Write> {
+    w: &'a mut W,
+}
+
+impl<'a, W: Write> XmlEscaper<'a, W> {
+    /// Creates a new `XmlEscaper` that will write to `w`.
+    ///
+    /// # Arguments
+    /// * `w` - The `Write` to write to.
+    pub fn new(w: &'a mut W) -> Self {
+        Self { w }
+    }
+}
+
+impl<'a, W: Write> Write for XmlEscaper<'a, W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        let mut start = 0;
+        for (i, c) in s.char_indices() {
+            let escaped = match c {
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '&' => "&amp;",
+                '"' => "&quot;",
+                '\'' => "&apos;",
+                _ => continue,
+            };
+            self.w.write_str(&s[start..i])?;
+            self.w.write_str(escaped)?;
+            start = i + 1;
+        }
+        self.w.write_str(&s[start..])
+    }
+}
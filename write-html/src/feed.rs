@@ -0,0 +1,448 @@
+/*!
+Provides [`Feed`] and [`Entry`], to write RSS 2.0 and Atom 1.0 feeds.
+
+The content of the entries is any [`Html`], so that the template of an article can be used for
+both its page and the feeds.
+It is written as escaped HTML, in `<content:encoded>` for RSS and in `<content type="html">` for Atom.
+The feeds are always written as XML (see [`xml`](crate::xml)), whatever the environment.
+
+# Example
+```
+use write_html::*;
+use write_html::feed::{Feed, Entry, Person};
+
+fn article(text: &str) -> impl Html + '_ {
+    html!(p { (text.as_html_text()) })
+}
+
+let date = DateTime::from_ymd_hms(2024, 3, 1, 12, 0, 0).unwrap();
+let feed = || Feed::new("Blog", "https://example.com/")
+    .with_self_link("https://example.com/feed.xml")
+    .with_author(Person::new("Ada"))
+    .with_entry(
+        Entry::new("First post", "https://example.com/first")
+            .with_published(date)
+            .with_content(article("Hello & welcome"))
+    );
+
+assert_eq!(xml::to_string(feed().rss()).unwrap(), concat!(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+    "<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\"",
+    " xmlns:content=\"http://purl.org/rss/1.0/modules/content/\"",
+    " xmlns:dc=\"http://purl.org/dc/elements/1.1/\"><channel>",
+    "<title>Blog</title><link>https://example.com/</link><description></description>",
+    "<lastBuildDate>Fri, 01 Mar 2024 12:00:00 +0000</lastBuildDate>",
+    "<atom:link href=\"https://example.com/feed.xml\" rel=\"self\" type=\"application/rss+xml\"/>",
+    "<dc:creator>Ada</dc:creator>",
+    "<item><title>First post</title><link>https://example.com/first</link>",
+    "<guid isPermaLink=\"true\">https://example.com/first</guid>",
+    "<pubDate>Fri, 01 Mar 2024 12:00:00 +0000</pubDate>",
+    "<content:encoded><![CDATA[<p>Hello &amp; welcome</p>]]></content:encoded></item>",
+    "</channel></rss>",
+));
+
+assert_eq!(feed().atom().to_html_string().unwrap(), concat!(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+    "<feed xmlns=\"http://www.w3.org/2005/Atom\">",
+    "<title>Blog</title><link href=\"https://example.com/\"/>",
+    "<link href=\"https://example.com/feed.xml\" rel=\"self\"/>",
+    "<id>https://example.com/feed.xml</id><updated>2024-03-01T12:00:00Z</updated>",
+    "<author><name>Ada</name></author>",
+    "<entry><title>First post</title><link href=\"https://example.com/first\"/>",
+    "<id>https://example.com/first</id><published>2024-03-01T12:00:00Z</published>",
+    "<updated>2024-03-01T12:00:00Z</updated>",
+    "<content type=\"html\">&lt;p&gt;Hello &amp;amp; welcome&lt;/p&gt;</content></entry>",
+    "</feed>",
+));
+```
+*/
+
+use crate::{xml::{self, text_element}, Compactability, DateTime, Empty, Error, Html, HtmlEnv, Serialization, SerializationEnv, Text, ToHtmlString};
+
+
+/// The namespace of Atom.
+pub const ATOM_NAMESPACE: &str = "http://www.w3.org/2005/Atom";
+
+/// The namespace of the RSS `content` module, for `<content:encoded>`.
+pub const CONTENT_NAMESPACE: &str = "http://purl.org/rss/1.0/modules/content/";
+
+/// The namespace of Dublin Core, for `<dc:creator>`.
+pub const DC_NAMESPACE: &str = "http://purl.org/dc/elements/1.1/";
+
+/// The author of a feed or an entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Person {
+    pub(crate) name: String,
+    pub(crate) email: Option<String>,
+    pub(crate) uri: Option<String>,
+}
+
+impl Person {
+    /// Creates a new `Person` with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), email: None, uri: None }
+    }
+
+    /// Sets the email address.
+    ///
+    /// In RSS, authors with an email address are written in `<author>` (or `<managingEditor>` for
+    /// the feed), and the others in `<dc:creator>`.
+    pub fn with_email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    /// Sets the URI of the home page, which is written in Atom and in JSON-LD, but not in RSS.
+    pub fn with_uri(mut self, uri: impl Into<String>) -> Self {
+        self.uri = Some(uri.into());
+        self
+    }
+
+    /// Writes the person in RSS, where `email_element` is the element used if there is an email address.
+    fn write_rss(&self, env: &mut impl HtmlEnv, email_element: &str) -> Result<(), Error> {
+        match &self.email {
+            Some(email) => text_element(env, email_element, format_args!("{} ({})", email, self.name)),
+            None => text_element(env, "dc:creator", &self.name),
+        }
+    }
+
+    fn write_atom(&self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        let mut person = env.open_tag("author", Compactability::No)?.inner_html()?;
+        text_element(&mut person, "name", &self.name)?;
+        if let Some(email) = &self.email {
+            text_element(&mut person, "email", email)?;
+        }
+        if let Some(uri) = &self.uri {
+            text_element(&mut person, "uri", uri)?;
+        }
+        person.close()?;
+        Ok(())
+    }
+}
+
+/// An entry of a [`Feed`], like an article.
+///
+/// `H` is the type of the content, see [`Entry::with_content`].
+pub struct Entry<H: Html = Empty> {
+    title: String,
+    link: String,
+    id: Option<String>,
+    published: Option<DateTime>,
+    updated: Option<DateTime>,
+    summary: Option<String>,
+    authors: Vec<Person>,
+    categories: Vec<String>,
+    content: H,
+}
+
+impl Entry {
+    /// Creates a new `Entry` with the given title and link, without content.
+    pub fn new(title: impl Into<String>, link: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            link: link.into(),
+            id: None,
+            published: None,
+            updated: None,
+            summary: None,
+            authors: Vec::new(),
+            categories: Vec::new(),
+            content: Empty,
+        }
+    }
+}
+
+impl<H: Html> Entry<H> {
+    /// Sets the unique identifier, which is the link by default.
+    ///
+    /// In RSS, it is written in `<guid>`, with `isPermaLink="false"` if it differs from the link.
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the publication date.
+    pub fn with_published(mut self, published: DateTime) -> Self {
+        self.published = Some(published);
+        self
+    }
+
+    /// Sets the date of the last update, which is the publication date by default.
+    pub fn with_updated(mut self, updated: DateTime) -> Self {
+        self.updated = Some(updated);
+        self
+    }
+
+    /// Sets the summary, written as text.
+    pub fn with_summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+
+    /// Adds an author.
+    pub fn with_author(mut self, author: Person) -> Self {
+        self.authors.push(author);
+        self
+    }
+
+    /// Adds a category.
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.categories.push(category.into());
+        self
+    }
+
+    /// Sets the content, which is written as escaped HTML.
+    pub fn with_content<C: Html>(self, content: C) -> Entry<C> {
+        Entry {
+            title: self.title,
+            link: self.link,
+            id: self.id,
+            published: self.published,
+            updated: self.updated,
+            summary: self.summary,
+            authors: self.authors,
+            categories: self.categories,
+            content,
+        }
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_deref().unwrap_or(&self.link)
+    }
+
+    /// Returns the date of the last update, if any.
+    fn updated(&self) -> Option<DateTime> {
+        self.updated.or(self.published)
+    }
+
+    /// Returns the content as an HTML string, if any.
+    fn content(self) -> Result<Option<String>, Error> {
+        if self.content.is_unit() {
+            return Ok(None);
+        }
+        self.content.to_html_string().map(Some)
+    }
+
+    fn write_rss(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        let mut item = env.open_tag("item", Compactability::No)?.inner_html()?;
+        text_element(&mut item, "title", &self.title)?;
+        text_element(&mut item, "link", &self.link)?;
+        let is_permalink = if self.id() == self.link { "true" } else { "false" };
+        item.child(xml::element("guid", [("isPermaLink", is_permalink)], Text(self.id())))?;
+        if let Some(published) = self.published {
+            text_element(&mut item, "pubDate", published.rfc822())?;
+        }
+        for author in &self.authors {
+            author.write_rss(&mut item, "author")?;
+        }
+        for category in &self.categories {
+            text_element(&mut item, "category", category)?;
+        }
+        if let Some(summary) = &self.summary {
+            text_element(&mut item, "description", summary)?;
+        }
+        if let Some(content) = self.content()? {
+            item.child(xml::element("content:encoded", Empty, xml::cdata(content)))?;
+        }
+        item.close()?;
+        Ok(())
+    }
+
+    fn write_atom(self, env: &mut impl HtmlEnv, feed_updated: DateTime) -> Result<(), Error> {
+        let mut entry = env.open_tag("entry", Compactability::No)?.inner_html()?;
+        text_element(&mut entry, "title", &self.title)?;
+        entry.child(xml::element("link", [("href", &self.link)], Empty))?;
+        text_element(&mut entry, "id", self.id())?;
+        if let Some(published) = self.published {
+            text_element(&mut entry, "published", published.rfc3339())?;
+        }
+        text_element(&mut entry, "updated", self.updated().unwrap_or(feed_updated).rfc3339())?;
+        for author in &self.authors {
+            author.write_atom(&mut entry)?;
+        }
+        for category in &self.categories {
+            entry.child(xml::element("category", [("term", category)], Empty))?;
+        }
+        if let Some(summary) = &self.summary {
+            text_element(&mut entry, "summary", summary)?;
+        }
+        if let Some(content) = self.content()? {
+            entry.child(xml::element("content", [("type", "html")], Text(content)))?;
+        }
+        entry.close()?;
+        Ok(())
+    }
+}
+
+/// A feed, written as RSS 2.0 with [`Feed::rss`] or as Atom 1.0 with [`Feed::atom`].
+///
+/// See the [module documentation](self) for an example.
+pub struct Feed<H: Html = Empty> {
+    title: String,
+    link: String,
+    self_link: Option<String>,
+    description: Option<String>,
+    language: Option<String>,
+    updated: Option<DateTime>,
+    authors: Vec<Person>,
+    entries: Vec<Entry<H>>,
+}
+
+impl<H: Html> Feed<H> {
+    /// Creates a new `Feed` with the given title and link to the website.
+    pub fn new(title: impl Into<String>, link: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            link: link.into(),
+            self_link: None,
+            description: None,
+            language: None,
+            updated: None,
+            authors: Vec::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Sets the URL of the feed itself, which is also the Atom identifier of the feed.
+    pub fn with_self_link(mut self, self_link: impl Into<String>) -> Self {
+        self.self_link = Some(self_link.into());
+        self
+    }
+
+    /// Sets the description, written as the Atom subtitle.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the language, like `en-US`.
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Sets the date of the last update, which is the latest date of the entries by default.
+    pub fn with_updated(mut self, updated: DateTime) -> Self {
+        self.updated = Some(updated);
+        self
+    }
+
+    /// Adds an author.
+    pub fn with_author(mut self, author: Person) -> Self {
+        self.authors.push(author);
+        self
+    }
+
+    /// Adds an entry.
+    pub fn with_entry(mut self, entry: Entry<H>) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    /// Adds entries.
+    pub fn with_entries(mut self, entries: impl IntoIterator<Item = Entry<H>>) -> Self {
+        self.entries.extend(entries);
+        self
+    }
+
+    /// Returns the feed as an RSS 2.0 document.
+    pub fn rss(self) -> Rss<H> {
+        Rss(self)
+    }
+
+    /// Returns the feed as an Atom 1.0 document.
+    pub fn atom(self) -> Atom<H> {
+        Atom(self)
+    }
+
+    /// Returns the date of the last update, if any.
+    fn updated(&self) -> Option<DateTime> {
+        self.updated.or_else(|| {
+            self.entries.iter().filter_map(Entry::updated).max_by_key(DateTime::timestamp)
+        })
+    }
+}
+
+/// A [`Feed`] written as an RSS 2.0 document, see [`Feed::rss`].
+pub struct Rss<H: Html>(Feed<H>);
+
+impl<H: Html> Html for Rss<H> {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        let feed = self.0;
+        let updated = feed.updated();
+        let mut env = SerializationEnv::new(env, Serialization::Xml);
+        env.write_html(xml::Declaration)?;
+
+        let mut rss = env.open_tag("rss", Compactability::No)?
+            .with_attr("version", "2.0")?
+            .with_attributes(xml::namespace("atom", ATOM_NAMESPACE))?
+            .with_attributes(xml::namespace("content", CONTENT_NAMESPACE))?
+            .with_attributes(xml::namespace("dc", DC_NAMESPACE))?
+            .inner_html()?;
+        let mut channel = rss.open_tag("channel", Compactability::No)?.inner_html()?;
+        text_element(&mut channel, "title", &feed.title)?;
+        text_element(&mut channel, "link", &feed.link)?;
+        text_element(&mut channel, "description", feed.description.as_deref().unwrap_or(""))?;
+        if let Some(language) = &feed.language {
+            text_element(&mut channel, "language", language)?;
+        }
+        if let Some(updated) = updated {
+            text_element(&mut channel, "lastBuildDate", updated.rfc822())?;
+        }
+        if let Some(self_link) = &feed.self_link {
+            let attributes = [("href", self_link.as_str()), ("rel", "self"), ("type", "application/rss+xml")];
+            channel.child(xml::element("atom:link", attributes, Empty))?;
+        }
+        for author in &feed.authors {
+            author.write_rss(&mut channel, "managingEditor")?;
+        }
+        for entry in feed.entries {
+            entry.write_rss(&mut channel)?;
+        }
+        channel.close()?;
+        rss.close()?;
+        Ok(())
+    }
+}
+
+/// A [`Feed`] written as an Atom 1.0 document, see [`Feed::atom`].
+///
+/// # Errors
+/// Writing it fails with [`Error::Validation`] if the feed has no date, since Atom requires the
+/// date of the last update.
+pub struct Atom<H: Html>(Feed<H>);
+
+impl<H: Html> Html for Atom<H> {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        let feed = self.0;
+        let updated = feed.updated()
+            .ok_or_else(|| Error::Validation(format!("the Atom feed \"{}\" has no date", feed.title)))?;
+        let mut env = SerializationEnv::new(env, Serialization::Xml);
+        env.write_html(xml::Declaration)?;
+
+        let mut opening = env.open_tag("feed", Compactability::No)?
+            .with_attributes(xml::default_namespace(ATOM_NAMESPACE))?;
+        if let Some(language) = &feed.language {
+            opening.attr("xml:lang", language)?;
+        }
+        let mut atom = opening.inner_html()?;
+        text_element(&mut atom, "title", &feed.title)?;
+        if let Some(description) = &feed.description {
+            text_element(&mut atom, "subtitle", description)?;
+        }
+        atom.child(xml::element("link", [("href", &feed.link)], Empty))?;
+        if let Some(self_link) = &feed.self_link {
+            atom.child(xml::element("link", [("href", self_link.as_str()), ("rel", "self")], Empty))?;
+        }
+        text_element(&mut atom, "id", feed.self_link.as_deref().unwrap_or(&feed.link))?;
+        text_element(&mut atom, "updated", updated.rfc3339())?;
+        for author in &feed.authors {
+            author.write_atom(&mut atom)?;
+        }
+        for entry in feed.entries {
+            entry.write_atom(&mut atom, updated)?;
+        }
+        atom.close()?;
+        Ok(())
+    }
+}
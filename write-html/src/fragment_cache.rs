@@ -0,0 +1,285 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::Mutex;
+
+use crate::{prerendered::BufferEnv, Assets, Html, HtmlEnv, Prerendered, Error, Serialization};
+
+
+/// A thread-safe cache of rendered fragments.
+///
+/// Fragments are stored by a user-provided key together with a version (or etag): a fragment
+/// is rendered again when it is requested with a different version than the cached one.
+/// When the cache is full, the least recently used fragments are evicted.
+///
+/// A fragment is rendered with the serialization and the context of the environment it is
+/// written into, and is rendered again when it is written with another serialization. The
+/// [`Assets`] it declares are cached with it, and declared again each time it is written.
+/// The context is not part of the key: a fragment that depends on it should have it in its key
+/// or its version.
+///
+/// The cache can be shared between threads, for example in a `static` or behind an
+/// [`Arc`](std::sync::Arc).
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let cache = FragmentCache::new(100);
+///
+/// let card = |name: &str, version: u64| {
+///     let name = name.to_owned();
+///     cache.get_or_render(name.clone(), version, move || html!(div .card { (name.as_html_text()) }))
+/// };
+///
+/// assert_eq!(card("apple", 1).to_html_string().unwrap(), "<div class=\"card\">apple</div>");
+/// assert_eq!(card("apple", 1).to_html_string().unwrap(), "<div class=\"card\">apple</div>");
+/// assert_eq!(card("apple", 2).to_html_string().unwrap(), "<div class=\"card\">apple</div>");
+///
+/// let stats = cache.stats();
+/// assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 1));
+/// ```
+pub struct FragmentCache<K, V = u64> {
+    inner: Mutex<Inner<K, V>>,
+    max_entries: usize,
+    max_bytes: usize,
+}
+
+struct Inner<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    /// The keys, ordered from the least to the most recently used.
+    lru: BTreeMap<u64, K>,
+    tick: u64,
+    bytes: usize,
+    hits: u64,
+    misses: u64,
+}
+
+struct Entry<V> {
+    version: V,
+    serialization: Serialization,
+    html: Prerendered,
+    assets: Assets,
+    tick: u64,
+}
+
+/// Statistics about a [`FragmentCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CacheStats {
+    /// The number of fragments found in the cache.
+    pub hits: u64,
+    /// The number of fragments that had to be rendered.
+    pub misses: u64,
+    /// The number of fragments currently in the cache.
+    pub entries: usize,
+    /// The total length of the fragments currently in the cache, in bytes.
+    pub bytes: usize,
+}
+
+impl<K: Hash + Eq + Clone, V: PartialEq> FragmentCache<K, V> {
+    /// Creates a new cache holding at most `max_entries` fragments.
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    ///
+    /// let cache = FragmentCache::new(2);
+    /// let render = |key: u32| cache.get_or_render(key, 0, move || Text(key)).to_html_string().unwrap();
+    ///
+    /// render(1);
+    /// render(2);
+    /// render(1);
+    /// render(3); // evicts 2, the least recently used
+    /// render(1);
+    /// render(2);
+    ///
+    /// let stats = cache.stats();
+    /// assert_eq!((stats.hits, stats.misses, stats.entries), (2, 4, 2));
+    /// ```
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                entries: HashMap::new(),
+                lru: BTreeMap::new(),
+                tick: 0,
+                bytes: 0,
+                hits: 0,
+                misses: 0,
+            }),
+            max_entries,
+            max_bytes: usize::MAX,
+        }
+    }
+
+    /// Limits the total length of the cached fragments to `max_bytes`.
+    ///
+    /// A fragment longer than `max_bytes` is rendered each time, without evicting the others.
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    ///
+    /// let cache = FragmentCache::new(10).with_max_bytes(20);
+    /// let render = |key: &'static str, text: String| {
+    ///     cache.get_or_render(key, 0, move || text.as_html_text()).to_html_string().unwrap()
+    /// };
+    ///
+    /// render("a", "small".to_owned());
+    /// render("b", "small".to_owned());
+    /// render("c", "large".repeat(10));
+    ///
+    /// let stats = cache.stats();
+    /// assert_eq!((stats.entries, stats.bytes), (2, 10));
+    /// ```
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Returns the fragment for `key` if it is cached with `version`, otherwise renders it with `render`.
+    ///
+    /// The lookup (and the rendering) happens when the returned value is written.
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    ///
+    /// let cache = FragmentCache::new(10);
+    /// let widget = || cache.get_or_render("widget", 1, || html!(
+    ///     (Assets::new().with_stylesheet("/widget.css"))
+    ///     br;
+    /// ));
+    ///
+    /// // the fragment follows the serialization of the document
+    /// let mut env = SerializationEnv::new(String::new(), Serialization::Xhtml);
+    /// env.write_html(widget()).unwrap();
+    /// assert_eq!(env.into_inner(), "<br/>");
+    ///
+    /// // and its assets are declared again when it comes from the cache
+    /// let page = with_assets(|| html!(head { (HeadSlot) } body { (widget()) }));
+    /// assert_eq!(
+    ///     page.to_html_string().unwrap(),
+    ///     "<head><link rel=\"stylesheet\" href=\"/widget.css\"></head><body><br></body>"
+    /// );
+    /// let stats = cache.stats();
+    /// assert_eq!((stats.hits, stats.misses), (1, 2));
+    /// ```
+    pub fn get_or_render<H: Html, F: FnOnce() -> H>(&self, key: K, version: V, render: F) -> CachedHtml<'_, K, V, F> {
+        CachedHtml { cache: self, key, version, render }
+    }
+
+    /// Removes the fragment for `key` from the cache.
+    pub fn invalidate(&self, key: &K) {
+        let mut inner = self.lock();
+        if let Some(entry) = inner.entries.remove(key) {
+            inner.lru.remove(&entry.tick);
+            inner.bytes -= entry.html.as_str().len();
+        }
+    }
+
+    /// Removes all the fragments from the cache.
+    pub fn clear(&self) {
+        let mut inner = self.lock();
+        inner.entries.clear();
+        inner.lru.clear();
+        inner.bytes = 0;
+    }
+
+    /// Returns the statistics of the cache.
+    pub fn stats(&self) -> CacheStats {
+        let inner = self.lock();
+        CacheStats {
+            hits: inner.hits,
+            misses: inner.misses,
+            entries: inner.entries.len(),
+            bytes: inner.bytes,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner<K, V>> {
+        // the cache is always left in a consistent state, so a poisoned lock can be recovered
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn get(&self, key: &K, version: &V, serialization: Serialization) -> Option<(Prerendered, Assets)> {
+        let mut inner = self.lock();
+        let inner = &mut *inner;
+
+        let entry = match inner.entries.get_mut(key) {
+            Some(entry) if entry.version == *version && entry.serialization == serialization => entry,
+            _ => {
+                inner.misses += 1;
+                return None;
+            }
+        };
+        inner.hits += 1;
+
+        inner.tick += 1;
+        let k = inner.lru.remove(&entry.tick).unwrap();
+        inner.lru.insert(inner.tick, k);
+        entry.tick = inner.tick;
+
+        Some((entry.html.clone(), entry.assets.clone()))
+    }
+
+    fn insert(&self, key: K, entry: Entry<V>) {
+        // a fragment that can't fit by itself is not cached, instead of flushing the cache
+        if entry.html.as_str().len() > self.max_bytes || self.max_entries == 0 {
+            return;
+        }
+
+        let mut inner = self.lock();
+        let inner = &mut *inner;
+
+        inner.tick += 1;
+        inner.bytes += entry.html.as_str().len();
+        inner.lru.insert(inner.tick, key.clone());
+        if let Some(old) = inner.entries.insert(key, Entry { tick: inner.tick, ..entry }) {
+            inner.lru.remove(&old.tick);
+            inner.bytes -= old.html.as_str().len();
+        }
+
+        // the new entry is the most recently used, so it is never evicted
+        while inner.entries.len() > 1 && (inner.entries.len() > self.max_entries || inner.bytes > self.max_bytes) {
+            let Some((_, key)) = inner.lru.pop_first() else { break };
+            let entry = inner.entries.remove(&key).unwrap();
+            inner.bytes -= entry.html.as_str().len();
+        }
+    }
+}
+
+/// A fragment from a [`FragmentCache`].
+///
+/// See [`FragmentCache::get_or_render`].
+pub struct CachedHtml<'c, K, V, F> {
+    cache: &'c FragmentCache<K, V>,
+    key: K,
+    version: V,
+    render: F,
+}
+
+impl<'c, K, V, F, H> Html for CachedHtml<'c, K, V, F>
+where
+    K: Hash + Eq + Clone,
+    V: PartialEq,
+    F: FnOnce() -> H,
+    H: Html,
+{
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        let serialization = env.serialization();
+        let (html, assets) = match self.cache.get(&self.key, &self.version, serialization) {
+            Some(cached) => cached,
+            None => {
+                // render outside of the lock, so that other fragments can be used meanwhile
+                let mut buffer = BufferEnv::new(env);
+                buffer.write_html((self.render)())?;
+                let html = Prerendered::from_rendered(buffer.buffer);
+                let assets = buffer.assets;
+                let entry = Entry { version: self.version, serialization, html: html.clone(), assets: assets.clone(), tick: 0 };
+                self.cache.insert(self.key, entry);
+                (html, assets)
+            }
+        };
+        env.write_html(assets)?;
+        html.write_html(env)
+    }
+}
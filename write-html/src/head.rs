@@ -0,0 +1,447 @@
+use std::fmt::Write;
+
+use crate::{Compactability, Error, Html, HtmlEnv};
+
+
+/// A script of a [`Head`].
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let head = Head::new()
+///     .with_script(Script::new("/app.js").with_defer())
+///     .with_script(Script::module("/module.js"));
+/// assert!(head.to_html_string().unwrap().ends_with(concat!(
+///     "<script src=\"/app.js\" defer></script>",
+///     "<script src=\"/module.js\" type=\"module\"></script>",
+/// )));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Script {
+    src: String,
+    loading: ScriptLoading,
+    module: bool,
+}
+
+/// When a [`Script`] is run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ScriptLoading {
+    /// The parser waits for the script, or after the document is parsed for modules.
+    Default,
+    /// The script is run as soon as it is loaded.
+    Async,
+    /// The script is run after the document is parsed.
+    Defer,
+}
+
+impl Script {
+    /// Creates a classic script, which blocks the parser unless it is async or deferred.
+    pub fn new(src: impl Into<String>) -> Self {
+        Self { src: src.into(), loading: ScriptLoading::Default, module: false }
+    }
+
+    /// Creates a module script (`type="module"`), which is deferred unless it is async.
+    pub fn module(src: impl Into<String>) -> Self {
+        Self { module: true, ..Self::new(src) }
+    }
+
+    /// Runs the script as soon as it is loaded (`async`).
+    pub fn with_async(mut self) -> Self {
+        self.loading = ScriptLoading::Async;
+        self
+    }
+
+    /// Runs the script after the document is parsed (`defer`).
+    pub fn with_defer(mut self) -> Self {
+        self.loading = ScriptLoading::Defer;
+        self
+    }
+
+    /// Returns the URL of the script.
+    pub fn src(&self) -> &str {
+        &self.src
+    }
+
+    /// Tells whether the script blocks the parser.
+    fn is_blocking(&self) -> bool {
+        !self.module && self.loading == ScriptLoading::Default
+    }
+
+    /// Tells whether the script is run after the document is parsed.
+    fn is_deferred(&self) -> bool {
+        self.loading == ScriptLoading::Defer || (self.module && self.loading == ScriptLoading::Default)
+    }
+}
+
+impl Html for Script {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        let mut opening = env.open_tag("script", Compactability::No)?.with_attr("src", &self.src)?;
+        if self.module {
+            opening.attr("type", "module")?;
+        }
+        match self.loading {
+            ScriptLoading::Default => {}
+            ScriptLoading::Async => { opening.attr("async", ())?; }
+            ScriptLoading::Defer => { opening.attr("defer", ())?; }
+        }
+        opening.close()?;
+        Ok(())
+    }
+}
+
+/// An icon of a [`Head`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Icon {
+    rel: &'static str,
+    href: String,
+    sizes: Option<String>,
+    mime_type: Option<String>,
+}
+
+impl Icon {
+    /// Creates an icon (`rel="icon"`).
+    pub fn new(href: impl Into<String>) -> Self {
+        Self { rel: "icon", href: href.into(), sizes: None, mime_type: None }
+    }
+
+    /// Creates an icon for the home screen of iOS (`rel="apple-touch-icon"`).
+    pub fn apple_touch(href: impl Into<String>) -> Self {
+        Self { rel: "apple-touch-icon", ..Self::new(href) }
+    }
+
+    /// Sets the sizes of the icon, like `32x32` or `any`.
+    pub fn with_sizes(mut self, sizes: impl Into<String>) -> Self {
+        self.sizes = Some(sizes.into());
+        self
+    }
+
+    /// Sets the MIME type of the icon, like `image/svg+xml`.
+    pub fn with_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+}
+
+/// A `<link>` of a [`Head`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Link {
+    rel: &'static str,
+    href: String,
+    /// The other attributes.
+    attributes: Vec<(&'static str, String)>,
+}
+
+impl Link {
+    fn new(rel: &'static str, href: impl Into<String>) -> Self {
+        Self { rel, href: href.into(), attributes: Vec::new() }
+    }
+
+    fn with(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.attributes.push((name, value.into()));
+        self
+    }
+
+    pub(crate) fn stylesheet(href: impl Into<String>) -> Self {
+        Self::new("stylesheet", href)
+    }
+
+    /// Creates a `<link rel="preload">` of a resource of the given type.
+    pub(crate) fn preload(href: impl Into<String>, as_type: impl Into<String>) -> Self {
+        let as_type = as_type.into();
+        let is_font = as_type == "font";
+        let link = Link::new("preload", href).with("as", as_type);
+        if is_font {
+            // fonts are always fetched in CORS mode
+            return link.with("crossorigin", "");
+        }
+        link
+    }
+}
+
+impl Html for &Link {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        let mut opening = env.open_tag("link", Compactability::Yes { final_slash: false })?
+            .with_attr("rel", self.rel)?
+            .with_attr("href", &self.href)?;
+        for (name, value) in &self.attributes {
+            opening.attr(*name, value)?;
+        }
+        opening.close()?;
+        Ok(())
+    }
+}
+
+/// Writes the content of the `<head>` of a document, in the recommended order.
+///
+/// The tags are written in this order, so that the browser can start loading the page as
+/// soon as possible:
+/// 1. `<meta charset="utf-8">`, which must be in the first 1024 bytes of the document,
+/// 2. `<meta name="viewport">`,
+/// 3. `<title>`,
+/// 4. `<link rel="preconnect">`,
+/// 5. async scripts,
+/// 6. blocking scripts,
+/// 7. stylesheets,
+/// 8. `<link rel="preload">`,
+/// 9. deferred and module scripts,
+/// 10. the metadata that doesn't affect the loading (description, canonical URL, robots,
+///     theme color, icons and the other `<meta>` tags).
+///
+/// Adding the same stylesheet, script, link or icon twice writes it once, and setting a
+/// `<meta>` again replaces its content.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let page = html!(
+///     (Doctype)
+///     html lang="en" {
+///         head {
+///             (Head::new()
+///                 .with_title("About")
+///                 .with_title_template("%s | My site")
+///                 .with_description("Who we are")
+///                 .with_canonical("https://example.com/about")
+///                 .with_stylesheet("/style.css")
+///                 .with_preconnect("https://fonts.example.com")
+///                 .with_stylesheet("/style.css"))
+///         }
+///         body {}
+///     }
+/// );
+/// assert_eq!(page.to_html_string().unwrap(), concat!(
+///     "<!DOCTYPE html><html lang=\"en\"><head>",
+///     "<meta charset=\"utf-8\">",
+///     "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">",
+///     "<title>About | My site</title>",
+///     "<link rel=\"preconnect\" href=\"https://fonts.example.com\">",
+///     "<link rel=\"stylesheet\" href=\"/style.css\">",
+///     "<meta name=\"description\" content=\"Who we are\">",
+///     "<link rel=\"canonical\" href=\"https://example.com/about\">",
+///     "</head><body></body></html>",
+/// ));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Head {
+    viewport: Option<String>,
+    title: Option<String>,
+    title_template: Option<String>,
+    preconnects: Vec<Link>,
+    scripts: Vec<Script>,
+    stylesheets: Vec<Link>,
+    preloads: Vec<Link>,
+    canonical: Option<String>,
+    icons: Vec<Icon>,
+    /// The `<meta name="..." content="...">` tags, like the description.
+    metas: Vec<(String, String)>,
+}
+
+impl Default for Head {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Head {
+    /// Creates a new `Head`, with the charset and a viewport for mobile devices.
+    pub fn new() -> Self {
+        Self {
+            viewport: Some("width=device-width, initial-scale=1".to_owned()),
+            title: None,
+            title_template: None,
+            preconnects: Vec::new(),
+            scripts: Vec::new(),
+            stylesheets: Vec::new(),
+            preloads: Vec::new(),
+            canonical: None,
+            icons: Vec::new(),
+            metas: Vec::new(),
+        }
+    }
+
+    /// Sets the content of `<meta name="viewport">`, or removes it with `None`.
+    pub fn with_viewport(mut self, viewport: Option<&str>) -> Self {
+        self.viewport = viewport.map(str::to_owned);
+        self
+    }
+
+    /// Sets the title of the page.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the template of the title, where `%s` is replaced by the title of the page
+    /// (like `%s | My site`).
+    pub fn with_title_template(mut self, template: impl Into<String>) -> Self {
+        self.title_template = Some(template.into());
+        self
+    }
+
+    /// Sets the description of the page.
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    ///
+    /// let head = Head::new().with_description("Say \"hi\" & wave").to_html_string().unwrap();
+    /// assert!(head.contains("<meta name=\"description\" content=\"Say &quot;hi&quot; &amp; wave\">"));
+    /// ```
+    pub fn with_description(self, description: impl Into<String>) -> Self {
+        self.with_meta("description", description)
+    }
+
+    /// Sets the canonical URL of the page.
+    pub fn with_canonical(mut self, href: impl Into<String>) -> Self {
+        self.canonical = Some(href.into());
+        self
+    }
+
+    /// Sets the instructions for search engines, like `noindex, nofollow`.
+    pub fn with_robots(self, robots: impl Into<String>) -> Self {
+        self.with_meta("robots", robots)
+    }
+
+    /// Sets the color of the user interface around the page.
+    pub fn with_theme_color(self, color: impl Into<String>) -> Self {
+        self.with_meta("theme-color", color)
+    }
+
+    /// Adds a `<meta name="..." content="...">`, or replaces the content of the one with this name.
+    pub fn with_meta(mut self, name: impl Into<String>, content: impl Into<String>) -> Self {
+        let name = name.into();
+        let content = content.into();
+        match self.metas.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, c)) => *c = content,
+            None => self.metas.push((name, content)),
+        }
+        self
+    }
+
+    /// Adds an icon.
+    pub fn with_icon(mut self, icon: Icon) -> Self {
+        push_unique(&mut self.icons, icon);
+        self
+    }
+
+    /// Adds a `<link rel="preconnect">`, to connect early to another origin.
+    pub fn with_preconnect(mut self, href: impl Into<String>) -> Self {
+        push_unique(&mut self.preconnects, Link::new("preconnect", href));
+        self
+    }
+
+    /// Adds a `<link rel="preload">`, to load early a resource of the given type (like `font` or `image`).
+    pub fn with_preload(mut self, href: impl Into<String>, as_type: impl Into<String>) -> Self {
+        push_unique(&mut self.preloads, Link::preload(href, as_type));
+        self
+    }
+
+    /// Adds a stylesheet.
+    pub fn with_stylesheet(mut self, href: impl Into<String>) -> Self {
+        push_unique(&mut self.stylesheets, Link::stylesheet(href));
+        self
+    }
+
+    /// Adds a script.
+    ///
+    /// If a script with the same URL was added, only the first one is kept.
+    pub fn with_script(mut self, script: Script) -> Self {
+        push_script(&mut self.scripts, script);
+        self
+    }
+}
+
+/// Pushes `item` into `items`, unless it is already there.
+pub(crate) fn push_unique<T: PartialEq>(items: &mut Vec<T>, item: T) {
+    if !items.contains(&item) {
+        items.push(item);
+    }
+}
+
+/// Pushes `script` into `scripts`, unless a script with the same URL is already there.
+pub(crate) fn push_script(scripts: &mut Vec<Script>, script: Script) {
+    if !scripts.iter().any(|s| s.src == script.src) {
+        scripts.push(script);
+    }
+}
+
+/// Writes the scripts, the stylesheets and the preloads in the order of [`Head`].
+pub(crate) fn write_resources(
+    env: &mut impl HtmlEnv,
+    scripts: Vec<Script>,
+    stylesheets: &[Link],
+    preloads: &[Link],
+) -> Result<(), Error> {
+    let (deferred, scripts): (Vec<_>, Vec<_>) = scripts.into_iter().partition(Script::is_deferred);
+    let (blocking, async_scripts): (Vec<_>, Vec<_>) = scripts.into_iter().partition(Script::is_blocking);
+    for script in async_scripts.into_iter().chain(blocking) {
+        env.write_html(script)?;
+    }
+    for link in stylesheets.iter().chain(preloads) {
+        env.write_html(link)?;
+    }
+    for script in deferred {
+        env.write_html(script)?;
+    }
+    Ok(())
+}
+
+impl Html for Head {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        env.open_tag("meta", Compactability::Yes { final_slash: false })?
+            .with_attr("charset", "utf-8")?
+            .close()?;
+        if let Some(viewport) = &self.viewport {
+            write_meta(env, "viewport", viewport)?;
+        }
+
+        if let Some(title) = &self.title {
+            let mut inner = env.open_tag("title", Compactability::No)?.inner_html()?;
+            match &self.title_template {
+                Some(template) => {
+                    let (before, after) = template.split_once("%s").unwrap_or((template, ""));
+                    write!(inner.write_html_text(), "{}{}{}", before, title, after)?;
+                }
+                None => inner.write_html_text().write_str(title)?,
+            }
+            inner.close()?;
+        }
+
+        for link in &self.preconnects {
+            env.write_html(link)?;
+        }
+        write_resources(env, self.scripts, &self.stylesheets, &self.preloads)?;
+
+        if let Some(description) = self.metas.iter().find(|(name, _)| name == "description") {
+            write_meta(env, &description.0, &description.1)?;
+        }
+        if let Some(canonical) = self.canonical {
+            env.write_html(&Link::new("canonical", canonical))?;
+        }
+        for (name, content) in self.metas.iter().filter(|(name, _)| name != "description") {
+            write_meta(env, name, content)?;
+        }
+        for icon in self.icons {
+            let mut link = Link::new(icon.rel, icon.href);
+            if let Some(sizes) = icon.sizes {
+                link = link.with("sizes", sizes);
+            }
+            if let Some(mime_type) = icon.mime_type {
+                link = link.with("type", mime_type);
+            }
+            env.write_html(&link)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes a `<meta name="..." content="...">`.
+fn write_meta(env: &mut impl HtmlEnv, name: &str, content: &str) -> Result<(), Error> {
+    env.open_tag("meta", Compactability::Yes { final_slash: false })?
+        .with_attr("name", name)?
+        .with_attr("content", content)?
+        .close()?;
+    Ok(())
+}
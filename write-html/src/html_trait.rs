@@ -1,6 +1,7 @@
-use std::fmt::Write;
+use std::any::{Any, TypeId};
+use std::fmt::{Display, Write};
 
-use crate::{HtmlEnv, Empty, Sum};
+use crate::{Assets, HtmlEnv, Empty, Sum, Error, Serialization};
 
 
 /// Represents a content that can be written to a `Write` as HTML.
@@ -9,6 +10,28 @@ pub trait Html {
     fn is_unit(&self) -> bool {
         false
     }
+    /// Returns the bounds on the length of the written HTML, in bytes.
+    ///
+    /// Like [`Iterator::size_hint`], it returns a lower bound and an optional upper bound.
+    /// It is used by [`ToHtmlString::to_html_string`] to preallocate the string.
+    ///
+    /// The default implementation returns `(0, None)`.
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    ///
+    /// let page = tags::p(Empty, "Hello".as_html());
+    /// assert_eq!(page.size_hint(), (12, Some(12)));
+    ///
+    /// // presize the buffer
+    /// let mut s = String::with_capacity(page.size_hint().0);
+    /// s.write_html(page).unwrap();
+    /// assert_eq!(s, "<p>Hello</p>");
+    /// ```
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
     /// Writes the HTML representation of `self` to `w`.
     ///
     /// # Arguments
@@ -23,20 +46,36 @@ pub trait Html {
     /// "<h1>H1</h1>".as_html().write_html(&mut s).unwrap();
     /// assert_eq!(s, "<h1>H1</h1>");
     /// ```
-    fn write_html(self, env: &mut impl HtmlEnv) -> std::fmt::Result;
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error>;
+}
+
+/// Adds two size hints, as returned by [`Html::size_hint`].
+pub fn add_size_hints(a: (usize, Option<usize>), b: (usize, Option<usize>)) -> (usize, Option<usize>) {
+    let lower = a.0.saturating_add(b.0);
+    let upper = match (a.1, b.1) {
+        (Some(a), Some(b)) => a.checked_add(b),
+        _ => None,
+    };
+    (lower, upper)
 }
 
 impl Html for Empty {
     fn is_unit(&self) -> bool {
         true
     }
-    fn write_html(self, _env: &mut impl HtmlEnv) -> std::fmt::Result {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(0))
+    }
+    fn write_html(self, _env: &mut impl HtmlEnv) -> Result<(), Error> {
         Ok(())
     }
 }
 
 impl<A: Html, B: Html> Html for Sum<A, B> {
-    fn write_html(self, env: &mut impl HtmlEnv) -> std::fmt::Result {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        add_size_hints(self.0.size_hint(), self.1.size_hint())
+    }
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
         self.0.write_html(env)?;
         self.1.write_html(env)?;
         Ok(())
@@ -47,7 +86,7 @@ impl<A: Html, B: Html> Html for Sum<A, B> {
     fn is_unit(&self) -> bool {
         true
     }
-    fn write_html(self, _env: &mut impl HtmlEnv) -> std::fmt::Result {
+    fn write_html(self, _env: &mut impl HtmlEnv) -> Result<(), Error> {
         Ok(())
     }
 }*/
@@ -56,7 +95,14 @@ impl<I: Iterator> Html for I
 where
     I::Item: Html,
 {
-    fn write_html(self, env: &mut impl HtmlEnv) -> std::fmt::Result {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // the items can't be inspected without consuming the iterator
+        match Iterator::size_hint(self) {
+            (_, Some(0)) => (0, Some(0)),
+            _ => (0, None),
+        }
+    }
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
         for h in self {
             h.write_html(env)?;
         }
@@ -68,7 +114,7 @@ where
 //where
 //    I::Item: Html,
 //{
-//    fn write_html(self, env: &mut impl HtmlEnv) -> std::fmt::Result {
+//    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
 //        for h in self {
 //            h.write_html(env)?;
 //        }
@@ -76,6 +122,138 @@ where
 //    }
 //}
 
+/// The object-safe part of [`HtmlEnv`], used by [`HtmlWriter`].
+trait DynHtmlEnv: Write {
+    fn dyn_serialization(&self) -> Serialization;
+    fn dyn_before_open_tag(&mut self, tag: &str) -> Result<(), Error>;
+    fn dyn_after_open_tag(&mut self, tag: &str) -> Result<(), Error>;
+    fn dyn_before_close_tag(&mut self, tag: &str) -> Result<(), Error>;
+    fn dyn_after_close_tag(&mut self, tag: &str) -> Result<(), Error>;
+    fn dyn_assets(&mut self) -> Option<&mut Assets>;
+    fn dyn_implicit_close(&mut self, tag: &str);
+    fn dyn_context_value(&self, type_id: TypeId) -> Option<&dyn Any>;
+}
+
+impl<E: HtmlEnv> DynHtmlEnv for E {
+    fn dyn_serialization(&self) -> Serialization {
+        self.serialization()
+    }
+    fn dyn_before_open_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.before_open_tag(tag)
+    }
+    fn dyn_after_open_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.after_open_tag(tag)
+    }
+    fn dyn_before_close_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.before_close_tag(tag)
+    }
+    fn dyn_after_close_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.after_close_tag(tag)
+    }
+    fn dyn_assets(&mut self) -> Option<&mut Assets> {
+        self.assets()
+    }
+    fn dyn_implicit_close(&mut self, tag: &str) {
+        self.implicit_close(tag)
+    }
+    fn dyn_context_value(&self, type_id: TypeId) -> Option<&dyn Any> {
+        self.context_value(type_id)
+    }
+}
+
+/// A type-erased [`HtmlEnv`], passed to closure-based components.
+///
+/// See [`from_fn`].
+pub struct HtmlWriter<'a> {
+    env: &'a mut dyn DynHtmlEnv,
+}
+
+impl<'a> HtmlWriter<'a> {
+    /// Creates a new `HtmlWriter` that will write to `env`.
+    pub fn new(env: &'a mut impl HtmlEnv) -> Self {
+        Self { env }
+    }
+}
+
+impl<'a> Write for HtmlWriter<'a> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.env.write_str(s)
+    }
+}
+
+impl<'a> HtmlEnv for HtmlWriter<'a> {
+    fn serialization(&self) -> Serialization {
+        self.env.dyn_serialization()
+    }
+    fn before_open_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.env.dyn_before_open_tag(tag)
+    }
+    fn after_open_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.env.dyn_after_open_tag(tag)
+    }
+    fn before_close_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.env.dyn_before_close_tag(tag)
+    }
+    fn after_close_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.env.dyn_after_close_tag(tag)
+    }
+    fn assets(&mut self) -> Option<&mut Assets> {
+        self.env.dyn_assets()
+    }
+    fn implicit_close(&mut self, tag: &str) {
+        self.env.dyn_implicit_close(tag)
+    }
+    fn context_value(&self, type_id: TypeId) -> Option<&dyn Any> {
+        self.env.dyn_context_value(type_id)
+    }
+}
+
+/// Html written by a closure.
+///
+/// See [`from_fn`].
+pub struct HtmlFn<F>(pub F);
+
+impl<F> Html for HtmlFn<F>
+where
+    F: FnOnce(&mut HtmlWriter) -> Result<(), Error>,
+{
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        (self.0)(&mut HtmlWriter::new(env))
+    }
+}
+
+/// Creates an [`Html`] from a closure that writes into the environment.
+///
+/// This lets you mix imperative rendering code (loops with state, early exits...) into a
+/// declarative tree.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let items = ["a", "b", "c"];
+/// let s = html!(
+///     ul {
+///         (from_fn(|env| {
+///             for (i, item) in items.iter().enumerate() {
+///                 if i == 2 {
+///                     break;
+///                 }
+///                 env.write_html(tags::li(Empty, item.as_html_text()))?;
+///             }
+///             Ok(())
+///         }))
+///     }
+/// ).to_html_string().unwrap();
+/// assert_eq!(s, "<ul><li>a</li><li>b</li></ul>");
+/// ```
+pub fn from_fn<F>(f: F) -> HtmlFn<F>
+where
+    F: FnOnce(&mut HtmlWriter) -> Result<(), Error>,
+{
+    HtmlFn(f)
+}
+
 /// Html string
 ///
 /// TODO better doc
@@ -85,8 +263,13 @@ impl<S> Html for HtmlStr<S>
 where
     S: AsRef<str>,
 {
-    fn write_html(self, env: &mut impl HtmlEnv) -> std::fmt::Result {
-        env.write_str(self.0.as_ref())
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.as_ref().len();
+        (len, Some(len))
+    }
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        env.write_str(self.0.as_ref())?;
+        Ok(())
     }
 }
 
@@ -99,14 +282,112 @@ impl<S> Html for HtmlTextStr<S>
 where
     S: AsRef<str>,
 {
-    fn write_html(self, env: &mut impl HtmlEnv) -> std::fmt::Result {
-        env.write_html_text().write_str(self.0.as_ref())
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // every byte is escaped into at most 5 bytes (`&amp;`)
+        let len = self.0.as_ref().len();
+        (len, len.checked_mul(5))
+    }
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        env.write_html_text().write_str(self.0.as_ref())?;
+        Ok(())
+    }
+}
+
+/// Html that may fail to be produced.
+///
+/// The error is returned when the HTML is written, as an [`Error::User`].
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// fn user_name(id: u32) -> Result<&'static str, String> {
+///     match id {
+///         0 => Ok("admin"),
+///         _ => Err(format!("unknown user {}", id)),
+///     }
+/// }
+///
+/// let s = html!(p { (TryHtml(user_name(0).map(|n| n.as_html_text()))) }).to_html_string().unwrap();
+/// assert_eq!(s, "<p>admin</p>");
+///
+/// let err = html!(p { (TryHtml(user_name(1).map(|n| n.as_html_text()))) }).to_html_string().unwrap_err();
+/// assert_eq!(err.to_string(), "unknown user 1 (in p)");
+/// ```
+pub struct TryHtml<H, E>(pub Result<H, E>);
+
+impl<H, E> Html for TryHtml<H, E>
+where
+    H: Html,
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.0 {
+            Ok(h) => h.size_hint(),
+            Err(_) => (0, Some(0)),
+        }
+    }
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        match self.0 {
+            Ok(h) => h.write_html(env),
+            Err(e) => Err(Error::user(e)),
+        }
+    }
+}
+
+/// Html text written through [`Display`], escaped as necessary.
+///
+/// Unlike `format!("{}", x).as_html_text()`, the value is formatted directly into the
+/// environment, without an intermediate [`String`].
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let s = tags::p(Empty, Text(42)).to_html_string().unwrap();
+/// assert_eq!(s, "<p>42</p>");
+///
+/// let s = Text(format_args!("{} < {}", 1, 2)).to_html_string().unwrap();
+/// assert_eq!(s, "1 &lt; 2");
+///
+/// // non-string literals in `html!` are written as `Text`
+/// let s = html!(td colspan=2 { 42 '<' 1.5 }).to_html_string().unwrap();
+/// assert_eq!(s, "<td colspan=\"2\">42&lt;1.5</td>");
+///
+/// // including negative numbers and booleans
+/// let s = html!(td { -1 " " true }).to_html_string().unwrap();
+/// assert_eq!(s, "<td>-1 true</td>");
+/// ```
+///
+/// Numbers don't implement [`Html`] themselves: it would conflict with the implementation for
+/// iterators, since the standard library could make them iterators. Write them with `Text(n)`
+/// or [`n.as_html_text()`](AsHtml::as_html_text) instead.
+pub struct Text<T>(pub T);
+
+impl<T: Display> Html for Text<T> {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        write!(env.write_html_text(), "{}", self.0)?;
+        Ok(())
+    }
+}
+
+/// Html written through [`Display`], without escaping.
+///
+/// This is the [`Display`] counterpart of [`HtmlStr`], see [`Text`] for the escaped version.
+pub struct HtmlDisplay<T>(pub T);
+
+impl<T: Display> Html for HtmlDisplay<T> {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        write!(env, "{}", self.0)?;
+        Ok(())
     }
 }
 
 /// Something that can be converted into HTML.
 ///
 /// TODO better doc
+// `as_html` takes `self` by value, like the `&str` it was made for
+#[allow(clippy::wrong_self_convention)]
 pub trait AsHtml {
     /// The HTML type.
     type Html: Html;
@@ -140,15 +421,35 @@ impl AsHtml for String {
     }
 }
 
+// numbers and `char` go through `AsHtml`, see `Text`
+macro_rules! impl_as_html_display {
+    ($($t:ty)*) => {
+        $(
+            impl AsHtml for $t {
+                type Html = HtmlDisplay<$t>;
+                type HtmlText = Text<$t>;
+                fn as_html(self) -> Self::Html {
+                    HtmlDisplay(self)
+                }
+                fn as_html_text(self) -> Self::HtmlText {
+                    Text(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_as_html_display!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize f32 f64 char);
+
 /// Something that can be converted into an HTML string.
 pub trait ToHtmlString {
     /// Converts `self` into an HTML string.
-    fn to_html_string(self) -> Result<String, std::fmt::Error>;
+    fn to_html_string(self) -> Result<String, Error>;
 }
 
 impl<H: Html> ToHtmlString for H {
-    fn to_html_string(self) -> Result<String, std::fmt::Error> {
-        let mut s = String::new();
+    fn to_html_string(self) -> Result<String, Error> {
+        let mut s = String::with_capacity(self.size_hint().0);
         self.write_html(&mut s)?;
         Ok(s)
     }
@@ -0,0 +1,155 @@
+use std::fmt::Write;
+use std::io::{self, BufWriter};
+
+use crate::{Html, HtmlEnv, Error};
+
+
+/// An [`HtmlEnv`] that writes into an [`io::Write`], for example a file or a socket.
+///
+/// The output is buffered, and the [`io::Error`]s of the underlying writer are preserved
+/// instead of being collapsed into a [`std::fmt::Error`], when the HTML is written with
+/// [`HtmlEnv::write_html`] (or its alias [`IoHtmlEnv::render`]). Writing with
+/// [`Write::write_str`], or through another environment, only gives a [`std::fmt::Error`].
+/// The buffered output is flushed when the environment is dropped, but errors are then
+/// ignored: use [`IoHtmlEnv::flush`] or [`IoHtmlEnv::into_inner`] to get them.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let mut env = IoHtmlEnv::new(Vec::new());
+/// env.render(html!(p { "Hello, world!" })).unwrap();
+/// let bytes = env.into_inner().unwrap();
+/// assert_eq!(bytes, b"<p>Hello, world!</p>");
+/// ```
+pub struct IoHtmlEnv<W: io::Write> {
+    w: BufWriter<W>,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> IoHtmlEnv<W> {
+    /// Creates a new `IoHtmlEnv` that will write to `w`, with the default buffer capacity.
+    pub fn new(w: W) -> Self {
+        Self { w: BufWriter::new(w), error: None }
+    }
+
+    /// Creates a new `IoHtmlEnv` that will write to `w`, with a buffer of the given capacity.
+    pub fn with_capacity(capacity: usize, w: W) -> Self {
+        Self { w: BufWriter::with_capacity(capacity, w), error: None }
+    }
+
+    /// Writes an [`Html`] into the environment, like [`HtmlEnv::write_html`].
+    ///
+    /// If the underlying writer fails, its error is returned as an [`Error::Io`].
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    /// use std::io;
+    ///
+    /// struct Full;
+    /// impl io::Write for Full {
+    ///     fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+    ///         Err(io::Error::new(io::ErrorKind::StorageFull, "disk full"))
+    ///     }
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut env = IoHtmlEnv::with_capacity(0, Full);
+    /// let err = env.render(html!(p { "Hello, world!" })).err().unwrap();
+    /// assert!(matches!(err.innermost(), Error::Io(_)));
+    /// assert_eq!(io::Error::from(err).kind(), io::ErrorKind::StorageFull);
+    ///
+    /// // the io error is kept through `HtmlEnv::write_html` too, and not reported again later
+    /// let err = env.write_html(html!(p { "Hello" })).err().unwrap();
+    /// assert!(matches!(err.innermost(), Error::Io(_)));
+    ///
+    /// // a failed `write_str` only gives a `std::fmt::Error`...
+    /// use std::fmt::Write;
+    /// assert!(env.write_str("Hello").is_err());
+    ///
+    /// // ...whose io error is not mistaken for the cause of a later, unrelated error
+    /// struct Failing;
+    /// impl std::fmt::Display for Failing {
+    ///     fn fmt(&self, _f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    ///         Err(std::fmt::Error)
+    ///     }
+    /// }
+    /// let err = env.render(Text(Failing)).err().unwrap();
+    /// assert!(matches!(err.innermost(), Error::Fmt));
+    /// ```
+    pub fn render(&mut self, html: impl Html) -> Result<&mut Self, Error> {
+        HtmlEnv::write_html(self, html)
+    }
+
+    /// Flushes the buffered output into the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        io::Write::flush(&mut self.w)
+    }
+
+    /// Flushes the buffered output and returns the underlying writer.
+    pub fn into_inner(self) -> io::Result<W> {
+        self.w.into_inner().map_err(|e| e.into_error())
+    }
+}
+
+impl<W: io::Write> Write for IoHtmlEnv<W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        io::Write::write_all(&mut self.w, s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            std::fmt::Error
+        })
+    }
+}
+
+impl<W: io::Write> HtmlEnv for IoHtmlEnv<W> {
+    fn write_html(&mut self, html: impl Html) -> Result<&mut Self, Error> {
+        // an error left by a write that didn't go through here is not the one of this write
+        self.error = None;
+        match html.write_html(self) {
+            Ok(()) => Ok(self),
+            Err(e) => {
+                let io_error = self.error.take();
+                Err(e.map_innermost(|e| match (e, io_error) {
+                    (Error::Fmt, Some(io_error)) => Error::Io(io_error),
+                    (e, _) => e,
+                }))
+            }
+        }
+    }
+}
+
+/// An [`HtmlEnv`] that writes into a byte buffer.
+///
+/// Unlike writing to a [`String`] and converting it, or using an [`IoHtmlEnv`], the output
+/// is appended directly to the [`Vec<u8>`], without any UTF-8 validation or intermediate buffer.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let mut bytes = Vec::new();
+/// BytesHtmlEnv::new(&mut bytes).write_html(html!(p { "Hello, world!" })).unwrap();
+/// assert_eq!(bytes, b"<p>Hello, world!</p>");
+/// ```
+pub struct BytesHtmlEnv<'a> {
+    bytes: &'a mut Vec<u8>,
+}
+
+impl<'a> BytesHtmlEnv<'a> {
+    /// Creates a new `BytesHtmlEnv` that will append to `bytes`.
+    pub fn new(bytes: &'a mut Vec<u8>) -> Self {
+        Self { bytes }
+    }
+}
+
+impl<'a> Write for BytesHtmlEnv<'a> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.bytes.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl<'a> HtmlEnv for BytesHtmlEnv<'a> {}
@@ -0,0 +1,836 @@
+use std::fmt::Display;
+
+use crate::{Html, HtmlEnv, HtmlWriter, Compactability, Error};
+
+
+/// An error in the LaTeX source of a [`LatexMath`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatexError {
+    /// The byte offset in the source where the error was found.
+    pub position: usize,
+    /// What is wrong.
+    pub message: String,
+}
+
+impl Display for LatexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at byte {})", self.message, self.position)
+    }
+}
+
+impl std::error::Error for LatexError {}
+
+/// An equation written in LaTeX, rendered as [MathML](crate::tags::math).
+///
+/// The LaTeX is converted when the `LatexMath` is created, so that errors are reported early,
+/// and the equation is rendered by the browser without any script.
+///
+/// The supported subset of LaTeX covers:
+/// * fractions and roots (`\frac`, `\dfrac`, `\tfrac`, `\binom`, `\sqrt`, `\sqrt[n]`),
+/// * subscripts, superscripts and primes, with `\limits` and `\nolimits`,
+/// * Greek letters, common operators, relations, arrows and symbols (`\partial`, `\nabla`, `\infty`...),
+/// * big operators and integrals (`\sum`, `\prod`, `\int`, `\oint`...),
+/// * functions (`\sin`, `\log`, `\lim`, `\operatorname{...}`...),
+/// * accents (`\vec`, `\hat`, `\bar`, `\dot`, `\overline`...) and fonts (`\mathbf`, `\mathbb`, `\mathcal`...),
+/// * text (`\text{...}`), spaces (`\,`, `\quad`...) and fences (`\left( ... \right)`),
+/// * matrices and cases (`matrix`, `pmatrix`, `bmatrix`, `Bmatrix`, `vmatrix`, `Vmatrix`, `cases`, `aligned`).
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let energy = LatexMath::inline("E = mc^2").unwrap();
+/// assert_eq!(
+///     energy.to_html_string().unwrap(),
+///     "<math><mi>E</mi><mo>=</mo><mi>m</mi><msup><mi>c</mi><mn>2</mn></msup></math>"
+/// );
+///
+/// let field = LatexMath::display(r"\vec{F} = \frac{\partial p}{\partial t}").unwrap();
+/// assert_eq!(
+///     field.to_html_string().unwrap(),
+///     concat!(
+///         "<math display=\"block\"><mover accent=\"true\"><mi>F</mi><mo stretchy=\"false\">→</mo></mover>",
+///         "<mo>=</mo><mfrac><mrow><mi>∂</mi><mi>p</mi></mrow><mrow><mi>∂</mi><mi>t</mi></mrow></mfrac></math>",
+///     )
+/// );
+///
+/// let err = LatexMath::inline(r"\frac{1}{2").unwrap_err();
+/// assert_eq!(err.to_string(), "missing `}` (at byte 10)");
+/// ```
+///
+/// In the [`html!`](crate::html) macro, the conversion errors can be reported with [`TryHtml`](crate::TryHtml):
+/// ```
+/// use write_html::*;
+///
+/// let s = html!(
+///     p { "Since " (TryHtml(LatexMath::inline(r"\alpha \leq \beta"))) "..." }
+/// ).to_html_string().unwrap();
+/// assert_eq!(s, "<p>Since <math><mi>α</mi><mo>≤</mo><mi>β</mi></math>...</p>");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatexMath {
+    root: Node,
+}
+
+impl LatexMath {
+    /// Converts inline math, like `$...$` in LaTeX.
+    pub fn inline(latex: &str) -> Result<Self, LatexError> {
+        Self::parse(latex, false)
+    }
+
+    /// Converts display math, like `\[...\]` in LaTeX.
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    ///
+    /// let identity = LatexMath::display(r"\begin{pmatrix} 1 & 0 \\ 0 & 1 \end{pmatrix}").unwrap();
+    /// assert_eq!(
+    ///     identity.to_html_string().unwrap(),
+    ///     concat!(
+    ///         "<math display=\"block\"><mrow><mo>(</mo><mtable>",
+    ///         "<mtr><mtd><mn>1</mn></mtd><mtd><mn>0</mn></mtd></mtr>",
+    ///         "<mtr><mtd><mn>0</mn></mtd><mtd><mn>1</mn></mtd></mtr>",
+    ///         "</mtable><mo>)</mo></mrow></math>",
+    ///     )
+    /// );
+    /// ```
+    pub fn display(latex: &str) -> Result<Self, LatexError> {
+        Self::parse(latex, true)
+    }
+
+    fn parse(latex: &str, display: bool) -> Result<Self, LatexError> {
+        let mut parser = Parser { src: latex, pos: 0, display };
+        let children = parser.parse_list(Until::End)?;
+        let mut root = Node::element("math", children);
+        if display {
+            root = root.with_attr("display", "block");
+        }
+        Ok(Self { root })
+    }
+}
+
+impl Html for LatexMath {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        (&self).write_html(env)
+    }
+}
+
+impl Html for &LatexMath {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        // the tree is written through `HtmlWriter`, as its depth is only known at runtime
+        self.root.write(&mut HtmlWriter::new(env))
+    }
+}
+
+/// A MathML element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    /// A token element (like `mi`, `mn` or `mo`), with its text.
+    Token {
+        tag: &'static str,
+        attributes: Vec<(&'static str, &'static str)>,
+        text: String,
+    },
+    /// An element with children.
+    Element {
+        tag: &'static str,
+        attributes: Vec<(&'static str, &'static str)>,
+        children: Vec<Node>,
+    },
+}
+
+impl Node {
+    fn token(tag: &'static str, text: impl Into<String>) -> Self {
+        Node::Token { tag, attributes: Vec::new(), text: text.into() }
+    }
+
+    fn element(tag: &'static str, children: Vec<Node>) -> Self {
+        Node::Element { tag, attributes: Vec::new(), children }
+    }
+
+    /// Wraps the nodes in an `mrow`, unless there is exactly one.
+    fn row(mut nodes: Vec<Node>) -> Self {
+        if nodes.len() == 1 {
+            nodes.pop().unwrap()
+        } else {
+            Node::element("mrow", nodes)
+        }
+    }
+
+    fn space(width: &'static str) -> Self {
+        Node::element("mspace", Vec::new()).with_attr("width", width)
+    }
+
+    fn with_attr(mut self, name: &'static str, value: &'static str) -> Self {
+        let attributes = match &mut self {
+            Node::Token { attributes, .. } | Node::Element { attributes, .. } => attributes,
+        };
+        attributes.retain(|(n, _)| *n != name);
+        attributes.push((name, value));
+        self
+    }
+
+    /// Applies a font to the letters and digits of the node, see [`Variant`].
+    fn with_variant(self, variant: Variant) -> Self {
+        match self {
+            Node::Token { tag: tag @ ("mi" | "mn"), attributes, text } => {
+                if variant == Variant::Normal {
+                    let token = Node::Token { tag, attributes, text };
+                    return if tag == "mi" { token.with_attr("mathvariant", "normal") } else { token };
+                }
+                let text = text.chars().map(|c| variant.map_char(c)).collect();
+                Node::Token { tag, attributes, text }
+            }
+            Node::Element { tag, attributes, children } => {
+                let children = children.into_iter().map(|c| c.with_variant(variant)).collect();
+                Node::Element { tag, attributes, children }
+            }
+            token => token,
+        }
+    }
+
+    fn write(&self, w: &mut HtmlWriter) -> Result<(), Error> {
+        match self {
+            Node::Token { tag, attributes, text } => {
+                let mut inside = w
+                    .open_tag(*tag, Compactability::No)?
+                    .with_attributes(attributes.iter().copied())?
+                    .inner_html()?;
+                inside.text(text)?;
+                inside.close()?;
+            }
+            Node::Element { tag, attributes, children } => {
+                let opening = w
+                    .open_tag(*tag, Compactability::Yes { final_slash: true })?
+                    .with_attributes(attributes.iter().copied())?;
+                if children.is_empty() {
+                    opening.close()?;
+                } else {
+                    let mut inside = opening.inner_html()?;
+                    for child in children {
+                        child.write(&mut HtmlWriter::new(&mut inside))?;
+                    }
+                    inside.close()?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A font, as selected by `\mathbf`, `\mathbb`...
+///
+/// Except for [`Variant::Normal`], the letters are replaced by the corresponding
+/// [Mathematical Alphanumeric Symbols](https://en.wikipedia.org/wiki/Mathematical_Alphanumeric_Symbols),
+/// as MathML Core only supports `mathvariant="normal"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Variant {
+    Normal,
+    Bold,
+    Italic,
+    BoldItalic,
+    Script,
+    Fraktur,
+    DoubleStruck,
+    SansSerif,
+    Monospace,
+}
+
+impl Variant {
+    fn from_command(command: &str) -> Option<Self> {
+        Some(match command {
+            "mathrm" => Variant::Normal,
+            "mathbf" => Variant::Bold,
+            "mathit" => Variant::Italic,
+            "boldsymbol" | "bm" => Variant::BoldItalic,
+            "mathcal" | "mathscr" => Variant::Script,
+            "mathfrak" => Variant::Fraktur,
+            "mathbb" => Variant::DoubleStruck,
+            "mathsf" => Variant::SansSerif,
+            "mathtt" => Variant::Monospace,
+            _ => return None,
+        })
+    }
+
+    fn map_char(self, c: char) -> char {
+        // the letters that were encoded before the Mathematical Alphanumeric Symbols block
+        let exception = match (self, c) {
+            (Variant::Italic, 'h') => Some('ℎ'),
+            (Variant::Script, 'B') => Some('ℬ'),
+            (Variant::Script, 'E') => Some('ℰ'),
+            (Variant::Script, 'F') => Some('ℱ'),
+            (Variant::Script, 'H') => Some('ℋ'),
+            (Variant::Script, 'I') => Some('ℐ'),
+            (Variant::Script, 'L') => Some('ℒ'),
+            (Variant::Script, 'M') => Some('ℳ'),
+            (Variant::Script, 'R') => Some('ℛ'),
+            (Variant::Script, 'e') => Some('ℯ'),
+            (Variant::Script, 'g') => Some('ℊ'),
+            (Variant::Script, 'o') => Some('ℴ'),
+            (Variant::Fraktur, 'C') => Some('ℭ'),
+            (Variant::Fraktur, 'H') => Some('ℌ'),
+            (Variant::Fraktur, 'I') => Some('ℑ'),
+            (Variant::Fraktur, 'R') => Some('ℜ'),
+            (Variant::Fraktur, 'Z') => Some('ℨ'),
+            (Variant::DoubleStruck, 'C') => Some('ℂ'),
+            (Variant::DoubleStruck, 'H') => Some('ℍ'),
+            (Variant::DoubleStruck, 'N') => Some('ℕ'),
+            (Variant::DoubleStruck, 'P') => Some('ℙ'),
+            (Variant::DoubleStruck, 'Q') => Some('ℚ'),
+            (Variant::DoubleStruck, 'R') => Some('ℝ'),
+            (Variant::DoubleStruck, 'Z') => Some('ℤ'),
+            _ => None,
+        };
+        if let Some(e) = exception {
+            return e;
+        }
+
+        // the first uppercase letter, lowercase letter and digit
+        let (upper, lower, digit) = match self {
+            Variant::Normal => return c,
+            Variant::Bold => (0x1D400, 0x1D41A, Some(0x1D7CE)),
+            Variant::Italic => (0x1D434, 0x1D44E, None),
+            Variant::BoldItalic => (0x1D468, 0x1D482, None),
+            Variant::Script => (0x1D49C, 0x1D4B6, None),
+            Variant::Fraktur => (0x1D504, 0x1D51E, None),
+            Variant::DoubleStruck => (0x1D538, 0x1D552, Some(0x1D7D8)),
+            Variant::SansSerif => (0x1D5A0, 0x1D5BA, Some(0x1D7E2)),
+            Variant::Monospace => (0x1D670, 0x1D68A, Some(0x1D7F6)),
+        };
+        let code = match (c, digit) {
+            ('A'..='Z', _) => upper + (c as u32 - 'A' as u32),
+            ('a'..='z', _) => lower + (c as u32 - 'a' as u32),
+            ('0'..='9', Some(digit)) => digit + (c as u32 - '0' as u32),
+            _ => return c,
+        };
+        char::from_u32(code).unwrap_or(c)
+    }
+}
+
+fn greek_letter(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "alpha" => "α", "beta" => "β", "gamma" => "γ", "delta" => "δ", "epsilon" => "ϵ",
+        "varepsilon" => "ε", "zeta" => "ζ", "eta" => "η", "theta" => "θ", "vartheta" => "ϑ",
+        "iota" => "ι", "kappa" => "κ", "lambda" => "λ", "mu" => "μ", "nu" => "ν", "xi" => "ξ",
+        "omicron" => "ο", "pi" => "π", "varpi" => "ϖ", "rho" => "ρ", "varrho" => "ϱ",
+        "sigma" => "σ", "varsigma" => "ς", "tau" => "τ", "upsilon" => "υ", "phi" => "ϕ",
+        "varphi" => "φ", "chi" => "χ", "psi" => "ψ", "omega" => "ω",
+        _ => return None,
+    })
+}
+
+/// Uppercase Greek letters, which are upright in LaTeX.
+fn upright_greek_letter(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "Gamma" => "Γ", "Delta" => "Δ", "Theta" => "Θ", "Lambda" => "Λ", "Xi" => "Ξ", "Pi" => "Π",
+        "Sigma" => "Σ", "Upsilon" => "Υ", "Phi" => "Φ", "Psi" => "Ψ", "Omega" => "Ω",
+        _ => return None,
+    })
+}
+
+/// Symbols that are written as identifiers.
+fn identifier_symbol(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "infty" => "∞", "partial" => "∂", "nabla" => "∇", "hbar" => "ℏ", "ell" => "ℓ",
+        "emptyset" | "varnothing" => "∅", "aleph" => "ℵ", "Re" => "ℜ", "Im" => "ℑ", "wp" => "℘",
+        "imath" => "ı", "jmath" => "ȷ",
+        _ => return None,
+    })
+}
+
+fn operator_symbol(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "cdot" => "⋅", "times" => "×", "div" => "÷", "pm" => "±", "mp" => "∓",
+        "leq" | "le" => "≤", "geq" | "ge" => "≥", "neq" | "ne" => "≠", "approx" => "≈",
+        "equiv" => "≡", "sim" => "∼", "simeq" => "≃", "cong" => "≅", "propto" => "∝",
+        "ll" => "≪", "gg" => "≫", "to" | "rightarrow" => "→", "leftarrow" | "gets" => "←",
+        "Rightarrow" => "⇒", "Leftarrow" => "⇐", "Leftrightarrow" => "⇔", "leftrightarrow" => "↔",
+        "implies" => "⟹", "iff" => "⟺", "mapsto" => "↦", "longrightarrow" => "⟶",
+        "uparrow" => "↑", "downarrow" => "↓", "in" => "∈", "notin" => "∉", "ni" => "∋",
+        "subset" => "⊂", "subseteq" => "⊆", "supset" => "⊃", "supseteq" => "⊇", "cup" => "∪",
+        "cap" => "∩", "setminus" => "∖", "wedge" | "land" => "∧", "vee" | "lor" => "∨",
+        "neg" | "lnot" => "¬", "forall" => "∀", "exists" => "∃", "nexists" => "∄",
+        "ldots" | "dots" => "…", "cdots" => "⋯", "vdots" => "⋮", "ddots" => "⋱", "circ" => "∘",
+        "ast" => "∗", "star" => "⋆", "bullet" => "∙", "oplus" => "⊕", "otimes" => "⊗",
+        "odot" => "⊙", "perp" => "⊥", "parallel" => "∥", "mid" => "∣", "langle" => "⟨",
+        "rangle" => "⟩", "lvert" | "rvert" | "vert" => "|", "lVert" | "rVert" | "Vert" => "‖",
+        "lfloor" => "⌊", "rfloor" => "⌋", "lceil" => "⌈", "rceil" => "⌉", "lbrace" => "{",
+        "rbrace" => "}", "prime" => "′", "dagger" => "†", "colon" => ":", "vdash" => "⊢",
+        "models" => "⊨", "angle" => "∠", "triangle" => "△", "therefore" => "∴", "because" => "∵",
+        "{" => "{", "}" => "}", "|" => "‖", "%" => "%", "$" => "$", "#" => "#", "&" => "&", "_" => "_",
+        _ => return None,
+    })
+}
+
+/// Operators whose scripts are written as limits in display math.
+fn big_operator(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "sum" => "∑", "prod" => "∏", "coprod" => "∐", "bigcup" => "⋃", "bigcap" => "⋂",
+        "bigoplus" => "⨁", "bigotimes" => "⨂", "bigvee" => "⋁", "bigwedge" => "⋀",
+        _ => return None,
+    })
+}
+
+fn integral(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "int" => "∫", "iint" => "∬", "iiint" => "∭", "oint" => "∮",
+        _ => return None,
+    })
+}
+
+/// Functions whose scripts are written as limits in display math.
+fn limit_function(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "lim" => "lim", "liminf" => "lim inf", "limsup" => "lim sup", "max" => "max",
+        "min" => "min", "sup" => "sup", "inf" => "inf", "det" => "det", "gcd" => "gcd", "Pr" => "Pr",
+        _ => return None,
+    })
+}
+
+fn is_function(name: &str) -> bool {
+    matches!(name,
+        "sin" | "cos" | "tan" | "cot" | "sec" | "csc" | "arcsin" | "arccos" | "arctan" | "sinh"
+        | "cosh" | "tanh" | "coth" | "log" | "ln" | "lg" | "exp" | "dim" | "ker" | "deg" | "hom" | "arg"
+    )
+}
+
+/// Accents, with whether they stretch over their base.
+fn accent(name: &str) -> Option<(&'static str, bool)> {
+    Some(match name {
+        "vec" => ("→", false), "hat" => ("^", false), "bar" => ("¯", false), "dot" => ("˙", false),
+        "ddot" => ("¨", false), "tilde" => ("˜", false), "check" => ("ˇ", false),
+        "widehat" => ("^", true), "widetilde" => ("˜", true), "overline" => ("‾", true),
+        "overrightarrow" => ("→", true), "overleftarrow" => ("←", true), "overbrace" => ("⏞", true),
+        _ => return None,
+    })
+}
+
+fn spacing(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "," => "0.1667em", ":" | ">" => "0.2222em", ";" => "0.2778em", "!" => "-0.1667em",
+        " " => "0.25em", "quad" => "1em", "qquad" => "2em",
+        _ => return None,
+    })
+}
+
+/// A converted atom, before its scripts.
+struct Atom {
+    node: Node,
+    /// Whether the scripts are written as limits, under and over the atom.
+    limits: bool,
+    /// Whether the atom is a function name, followed by its argument.
+    function: bool,
+}
+
+impl Atom {
+    fn new(node: Node) -> Self {
+        Self { node, limits: false, function: false }
+    }
+}
+
+/// Where a list of atoms ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Until {
+    End,
+    Brace,
+    Bracket,
+    Right,
+    Cell,
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    display: bool,
+}
+
+impl<'a> Parser<'a> {
+    fn error_at<T>(&self, position: usize, message: impl Into<String>) -> Result<T, LatexError> {
+        Err(LatexError { position, message: message.into() })
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, LatexError> {
+        self.error_at(self.pos, message)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    /// Returns the name of the command at the current position, without the backslash.
+    fn peek_command(&self) -> Option<&'a str> {
+        let rest = self.src[self.pos..].strip_prefix('\\')?;
+        let letters = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+        if letters > 0 {
+            Some(&rest[..letters])
+        } else {
+            rest.chars().next().map(|c| &rest[..c.len_utf8()])
+        }
+    }
+
+    fn parse_command_name(&mut self) -> Result<&'a str, LatexError> {
+        match self.peek_command() {
+            Some(name) => {
+                self.pos += 1 + name.len();
+                Ok(name)
+            }
+            None => self.error("missing command name after `\\`"),
+        }
+    }
+
+    /// Parses the atoms until `until`, which is not consumed.
+    fn parse_list(&mut self, until: Until) -> Result<Vec<Node>, LatexError> {
+        let mut nodes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let command = self.peek_command();
+            match (self.peek(), until) {
+                (None, Until::End) => break,
+                (None, Until::Brace) => return self.error("missing `}`"),
+                (None, Until::Bracket) => return self.error("missing `]`"),
+                (None, Until::Right) => return self.error("missing `\\right`"),
+                (None, Until::Cell) => return self.error("missing `\\end`"),
+                (Some('}'), Until::Brace) | (Some(']'), Until::Bracket) | (Some('&'), Until::Cell) => break,
+                (Some('\\'), Until::Right) if command == Some("right") => break,
+                (Some('\\'), Until::Cell) if matches!(command, Some("\\" | "end")) => break,
+                _ => {}
+            }
+            self.parse_scripted(&mut nodes)?;
+        }
+        Ok(nodes)
+    }
+
+    /// Parses an atom with its scripts, and pushes it to `nodes`.
+    fn parse_scripted(&mut self, nodes: &mut Vec<Node>) -> Result<(), LatexError> {
+        let Atom { node: base, mut limits, function } = self.parse_atom()?;
+
+        loop {
+            self.skip_whitespace();
+            match self.peek_command() {
+                Some("limits") => limits = true,
+                Some("nolimits") => limits = false,
+                _ => break,
+            }
+            self.parse_command_name()?;
+        }
+
+        let mut sub = None;
+        let mut sup = None;
+        loop {
+            self.skip_whitespace();
+            let start = self.pos;
+            match self.peek() {
+                Some('_') => {
+                    self.bump();
+                    if sub.is_some() {
+                        return self.error_at(start, "double subscript");
+                    }
+                    sub = Some(self.parse_argument()?);
+                }
+                Some('^') => {
+                    self.bump();
+                    if sup.is_some() {
+                        return self.error_at(start, "double superscript");
+                    }
+                    sup = Some(self.parse_argument()?);
+                }
+                Some('\'') if sup.is_none() => {
+                    let mut primes = String::new();
+                    while self.eat('\'') {
+                        primes.push('′');
+                    }
+                    sup = Some(Node::token("mo", primes));
+                }
+                _ => break,
+            }
+        }
+
+        nodes.push(match (sub, sup) {
+            (None, None) => base,
+            (Some(sub), None) => Node::element(if limits { "munder" } else { "msub" }, vec![base, sub]),
+            (None, Some(sup)) => Node::element(if limits { "mover" } else { "msup" }, vec![base, sup]),
+            (Some(sub), Some(sup)) => {
+                Node::element(if limits { "munderover" } else { "msubsup" }, vec![base, sub, sup])
+            }
+        });
+        if function {
+            // U+2061 FUNCTION APPLICATION
+            nodes.push(Node::token("mo", "\u{2061}"));
+        }
+        Ok(())
+    }
+
+    /// Parses the argument of a command or a script.
+    fn parse_argument(&mut self) -> Result<Node, LatexError> {
+        self.skip_whitespace();
+        if self.peek().is_none() {
+            return self.error("missing argument");
+        }
+        Ok(self.parse_atom()?.node)
+    }
+
+    /// Parses an argument between braces, returning its source.
+    fn parse_raw_argument(&mut self) -> Result<&'a str, LatexError> {
+        self.skip_whitespace();
+        if !self.eat('{') {
+            return self.error("expected `{`");
+        }
+        let start = self.pos;
+        let mut depth = 0;
+        loop {
+            match self.bump() {
+                Some('{') => depth += 1,
+                Some('}') if depth == 0 => return Ok(&self.src[start..self.pos - 1]),
+                Some('}') => depth -= 1,
+                Some(_) => {}
+                None => return self.error("missing `}`"),
+            }
+        }
+    }
+
+    fn parse_atom(&mut self) -> Result<Atom, LatexError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let c = match self.peek() {
+            Some(c) => c,
+            None => return self.error("missing argument"),
+        };
+
+        let node = match c {
+            '{' => {
+                self.bump();
+                let nodes = self.parse_list(Until::Brace)?;
+                self.bump();
+                Node::row(nodes)
+            }
+            '\\' => return self.parse_command(),
+            '0'..='9' | '.' if self.src[start..].trim_start_matches('.').starts_with(|c: char| c.is_ascii_digit()) => {
+                while self.peek().is_some_and(|c| c.is_ascii_digit())
+                    || (self.peek() == Some('.') && self.src[self.pos + 1..].starts_with(|c: char| c.is_ascii_digit()))
+                {
+                    self.bump();
+                }
+                Node::token("mn", &self.src[start..self.pos])
+            }
+            c if c.is_alphabetic() => {
+                self.bump();
+                Node::token("mi", c)
+            }
+            '}' | ']' | '&' => return self.error(format!("unexpected `{}`", c)),
+            // scripts without base
+            '^' | '_' | '\'' => Node::element("mrow", Vec::new()),
+            '~' => {
+                self.bump();
+                Node::token("mtext", "\u{A0}")
+            }
+            _ => {
+                self.bump();
+                let operator = match c {
+                    '-' => '−',
+                    '*' => '∗',
+                    c => c,
+                };
+                Node::token("mo", operator)
+            }
+        };
+        Ok(Atom::new(node))
+    }
+
+    fn parse_command(&mut self) -> Result<Atom, LatexError> {
+        let start = self.pos;
+        let name = self.parse_command_name()?;
+
+        let node = match name {
+            "frac" | "dfrac" | "tfrac" | "cfrac" => {
+                let numerator = self.parse_argument()?;
+                let denominator = self.parse_argument()?;
+                let frac = Node::element("mfrac", vec![numerator, denominator]);
+                match name {
+                    "dfrac" | "cfrac" => Node::element("mstyle", vec![frac]).with_attr("displaystyle", "true"),
+                    "tfrac" => Node::element("mstyle", vec![frac]).with_attr("displaystyle", "false"),
+                    _ => frac,
+                }
+            }
+            "binom" => {
+                let n = self.parse_argument()?;
+                let k = self.parse_argument()?;
+                Node::element("mrow", vec![
+                    Node::token("mo", "("),
+                    Node::element("mfrac", vec![n, k]).with_attr("linethickness", "0"),
+                    Node::token("mo", ")"),
+                ])
+            }
+            "sqrt" => {
+                self.skip_whitespace();
+                if self.eat('[') {
+                    let index = Node::row(self.parse_list(Until::Bracket)?);
+                    self.bump();
+                    let radicand = self.parse_argument()?;
+                    Node::element("mroot", vec![radicand, index])
+                } else {
+                    Node::element("msqrt", vec![self.parse_argument()?])
+                }
+            }
+            "left" => {
+                let open = self.parse_delimiter()?;
+                let content = self.parse_list(Until::Right)?;
+                self.parse_command_name()?;
+                let close = self.parse_delimiter()?;
+                let nodes = open.into_iter().chain(content).chain(close).collect();
+                Node::element("mrow", nodes)
+            }
+            "begin" => self.parse_environment(start)?,
+            "text" | "textrm" | "textnormal" | "mbox" => Node::token("mtext", self.parse_raw_argument()?),
+            "operatorname" => {
+                let name = self.parse_raw_argument()?;
+                return Ok(Atom { node: Node::token("mi", name), limits: false, function: true });
+            }
+            "underline" => {
+                let base = self.parse_argument()?;
+                Node::element("munder", vec![base, Node::token("mo", "_")]).with_attr("accentunder", "true")
+            }
+            "underbrace" => {
+                let base = self.parse_argument()?;
+                Node::element("munder", vec![base, Node::token("mo", "⏟")]).with_attr("accentunder", "true")
+            }
+            _ => {
+                if let Some(variant) = Variant::from_command(name) {
+                    self.parse_argument()?.with_variant(variant)
+                } else if let Some((accent, stretchy)) = accent(name) {
+                    let base = self.parse_argument()?;
+                    let mut accent = Node::token("mo", accent);
+                    if !stretchy {
+                        accent = accent.with_attr("stretchy", "false");
+                    }
+                    Node::element("mover", vec![base, accent]).with_attr("accent", "true")
+                } else if let Some(width) = spacing(name) {
+                    Node::space(width)
+                } else if let Some(letter) = greek_letter(name) {
+                    Node::token("mi", letter)
+                } else if let Some(letter) = upright_greek_letter(name) {
+                    Node::token("mi", letter).with_attr("mathvariant", "normal")
+                } else if let Some(symbol) = identifier_symbol(name) {
+                    Node::token("mi", symbol)
+                } else if let Some(operator) = operator_symbol(name) {
+                    Node::token("mo", operator)
+                } else if let Some(operator) = big_operator(name) {
+                    return Ok(Atom { node: Node::token("mo", operator), limits: self.display, function: false });
+                } else if let Some(operator) = integral(name) {
+                    Node::token("mo", operator)
+                } else if let Some(function) = limit_function(name) {
+                    return Ok(Atom { node: Node::token("mi", function), limits: self.display, function: true });
+                } else if is_function(name) {
+                    return Ok(Atom { node: Node::token("mi", name), limits: false, function: true });
+                } else if matches!(name, "right" | "end" | "\\" | "limits" | "nolimits") {
+                    return self.error_at(start, format!("unexpected `\\{}`", name));
+                } else {
+                    return self.error_at(start, format!("unknown command `\\{}`", name));
+                }
+            }
+        };
+        Ok(Atom::new(node))
+    }
+
+    /// Parses the delimiter after `\left` or `\right`, `None` for `.`.
+    fn parse_delimiter(&mut self) -> Result<Option<Node>, LatexError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let delimiter = match self.bump() {
+            Some('.') => return Ok(None),
+            Some(c @ ('(' | ')' | '[' | ']' | '|' | '/')) => c.to_string(),
+            Some('<') => "⟨".to_owned(),
+            Some('>') => "⟩".to_owned(),
+            Some('\\') => {
+                self.pos = start;
+                let name = self.parse_command_name()?;
+                match operator_symbol(name) {
+                    Some(d) if matches!(name,
+                        "{" | "}" | "|" | "langle" | "rangle" | "lvert" | "rvert" | "vert" | "lVert"
+                        | "rVert" | "Vert" | "lfloor" | "rfloor" | "lceil" | "rceil" | "lbrace" | "rbrace"
+                    ) => d.to_owned(),
+                    _ => return self.error_at(start, format!("invalid delimiter `\\{}`", name)),
+                }
+            }
+            _ => return self.error_at(start, "missing delimiter"),
+        };
+        Ok(Some(Node::token("mo", delimiter)))
+    }
+
+    /// Parses an environment, after `\begin`.
+    fn parse_environment(&mut self, start: usize) -> Result<Node, LatexError> {
+        let name = self.parse_raw_argument()?;
+        let (open, close, columnalign) = match name {
+            "matrix" => (None, None, None),
+            "pmatrix" => (Some("("), Some(")"), None),
+            "bmatrix" => (Some("["), Some("]"), None),
+            "Bmatrix" => (Some("{"), Some("}"), None),
+            "vmatrix" => (Some("|"), Some("|"), None),
+            "Vmatrix" => (Some("‖"), Some("‖"), None),
+            "cases" => (Some("{"), None, Some("left left")),
+            "aligned" => (None, None, Some("right left")),
+            _ => return self.error_at(start, format!("unknown environment `{}`", name)),
+        };
+
+        let mut rows = Vec::new();
+        let mut cells = Vec::new();
+        loop {
+            let cell = self.parse_list(Until::Cell)?;
+            cells.push(Node::element("mtd", cell));
+            if self.eat('&') {
+                continue;
+            }
+
+            // `\\` or `\end`
+            let command_start = self.pos;
+            let command = self.parse_command_name()?;
+            rows.push(Node::element("mtr", std::mem::take(&mut cells)));
+            self.skip_whitespace();
+            if command == "\\" && self.peek_command() != Some("end") {
+                continue;
+            }
+            if command == "\\" {
+                self.parse_command_name()?;
+            }
+            let end = self.parse_raw_argument()?;
+            if end != name {
+                return self.error_at(command_start, format!("`\\begin{{{}}}` ended by `\\end{{{}}}`", name, end));
+            }
+            break;
+        }
+
+        let mut table = Node::element("mtable", rows);
+        if let Some(columnalign) = columnalign {
+            table = table.with_attr("columnalign", columnalign);
+        }
+        if open.is_none() && close.is_none() {
+            return Ok(table);
+        }
+        let nodes = open.map(|o| Node::token("mo", o)).into_iter()
+            .chain(Some(table))
+            .chain(close.map(|c| Node::token("mo", c)))
+            .collect();
+        Ok(Node::element("mrow", nodes))
+    }
+}
@@ -12,14 +12,14 @@ let page = html!(
     (Doctype)
     html lang="en" {
         head {
-            (DefaultMeta)
-            title { "Website!" }
+            (Head::new().with_title("Website!"))
         }
         body {
             h1 #some-id { "H1" }
             h2 { "H2" }
             h3 { "H3" }
             p { "Paragraph" }
+            p { 42 }
             ol {
                 li { "Item 1" }
                 li { "Item 2" }
@@ -34,19 +34,48 @@ let page = html!(
 
 //#![warn(missing_docs)]
 
+use std::any::{Any, TypeId};
 use std::fmt::Write;
 
 mod attributes;
+mod error;
 mod tag;
 mod html_trait;
+mod prerendered;
+mod fragment_cache;
+mod io_env;
+mod pretty;
+mod minify;
+mod latex;
+mod serialization;
+mod date;
+mod head;
+mod assets;
+mod context;
 
 pub use attributes::*;
+pub use error::*;
 use escapes::HtmlEscaper;
 pub use tag::*;
 pub use html_trait::*;
+pub use prerendered::*;
+pub use fragment_cache::*;
+pub use io_env::*;
+pub use pretty::*;
+pub use minify::*;
+pub use latex::*;
+pub use serialization::*;
+pub use date::*;
+pub use head::*;
+pub use assets::*;
+pub use context::*;
 
 pub mod escapes;
 pub mod tags;
+pub mod xml;
+pub mod feed;
+pub mod sitemap;
+pub mod metadata;
 
 pub use write_html_macro::html;
 
@@ -61,7 +90,10 @@ pub struct Sum<A, B>(pub A, pub B);
 
 /// Represents an environment that can write HTML.
 ///
-/// This trait is implemented for everything that implements [`Write`], for example [`String`].
+/// This trait is implemented for [`String`], [`std::fmt::Formatter`] and the environments of
+/// this crate (like [`IoHtmlEnv`] or [`PrettyEnv`]).
+/// Every method has a default implementation, so any other [`Write`] can become an
+/// environment with an empty `impl HtmlEnv for MyWriter {}`.
 pub trait HtmlEnv: Write + Sized {
 
     /// Writes an [`Html`] into the environment.
@@ -74,23 +106,38 @@ pub trait HtmlEnv: Write + Sized {
     /// s.write_html("Hello, world!".as_html()).unwrap();
     /// assert_eq!(s, "Hello, world!");
     /// ```
-    fn write_html(&mut self, html: impl Html) -> Result<&mut Self, std::fmt::Error> {
+    fn write_html(&mut self, html: impl Html) -> Result<&mut Self, Error> {
         html.write_html(self)?;
         Ok(self)
     }
 
-    /// Writes the HTML5 doctype.
+    /// Writes the doctype, or the XML declaration, of the [`serialization`](HtmlEnv::serialization).
     ///
     /// # Example
     /// ```
-    /// use write_html::HtmlEnv;
+    /// use write_html::{HtmlEnv, Serialization, SerializationEnv};
     /// 
     /// let mut s = String::new();
-    /// s.doctype();
+    /// s.doctype().unwrap();
     /// assert_eq!(s, "<!DOCTYPE html>");
+    ///
+    /// let mut env = SerializationEnv::new(String::new(), Serialization::Xml);
+    /// env.doctype().unwrap();
+    /// assert_eq!(env.into_inner(), "<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
     /// ```
-    fn doctype(&mut self) {
-        write!(self, "<!DOCTYPE html>").unwrap();
+    fn doctype(&mut self) -> Result<(), Error> {
+        match self.serialization() {
+            Serialization::Html | Serialization::Xhtml => self.write_str("<!DOCTYPE html>")?,
+            Serialization::Xml => self.write_str(xml::DECLARATION)?,
+        }
+        Ok(())
+    }
+
+    /// Returns the syntax used to write tags, attributes and the doctype.
+    ///
+    /// This is [`Serialization::Html`] by default, see [`SerializationEnv`] to change it.
+    fn serialization(&self) -> Serialization {
+        Serialization::Html
     }
 
     /// Lets you write text into the HTML document, escaping it as necessary.
@@ -105,7 +152,8 @@ pub trait HtmlEnv: Write + Sized {
     /// assert_eq!(s, "Hello, &lt;world&gt;");
     /// ```
     fn write_html_text<'s>(&'s mut self) -> HtmlEscaper<'s, Self> {
-        HtmlEscaper::new(self)
+        let serialization = self.serialization();
+        HtmlEscaper::with_serialization(self, serialization)
     }
 
     /// Returns a tag opening, which lets you write attributes and inner HTML.
@@ -128,52 +176,178 @@ pub trait HtmlEnv: Write + Sized {
     /// ```
     fn open_tag<'s, 't>(
         &'s mut self,
-        tag: &'t str, // TODO non-static lifetime
+        tag: impl Into<TagName<'t>>,
         compactability: Compactability
-    ) -> Result<TagOpening<'s, 't, Self>, std::fmt::Error> {
+    ) -> Result<TagOpening<'s, 't, Self>, Error> {
         TagOpening::<'s, 't, Self>::new(tag, self, compactability)
     }
 
-    fn with_html_writer(self, f: impl FnOnce(&mut Self) -> std::fmt::Result) -> Result<Self, std::fmt::Error> {
+    fn with_html_writer(self, f: impl FnOnce(&mut Self) -> Result<(), Error>) -> Result<Self, Error> {
         let mut s = self;
         f(&mut s)?;
         Ok(s)
     }
+
+    /// Called before a tag is opened, that is before `<tag` is written.
+    ///
+    /// This and the other `*_tag` hooks let an environment follow the structure of the
+    /// document (see [`PrettyEnv`]). They do nothing by default.
+    fn before_open_tag(&mut self, _tag: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called after the opening of a non-compacted tag is written, that is after `<tag ...>`.
+    fn after_open_tag(&mut self, _tag: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called before the closing of a non-compacted tag is written, that is before `</tag>`.
+    fn before_close_tag(&mut self, _tag: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called after a tag is closed, that is after `</tag>`, or after `<tag ...>`/`<tag .../>` for compacted tags.
+    fn after_close_tag(&mut self, _tag: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called when a tag is closed by `Drop` instead of `close`, before its closing is written.
+    ///
+    /// This happens when the writing is interrupted by an error (with `?`) or a panic, and then
+    /// the markup before the closing is likely incomplete. It does nothing by default, an
+    /// environment can override it to log these tags (checking [`std::thread::panicking`]).
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    /// use std::fmt::Write;
+    ///
+    /// #[derive(Default)]
+    /// struct LoggingEnv { html: String, implicitly_closed: Vec<String> }
+    ///
+    /// impl Write for LoggingEnv {
+    ///     fn write_str(&mut self, s: &str) -> std::fmt::Result {
+    ///         self.html.write_str(s)
+    ///     }
+    /// }
+    ///
+    /// impl HtmlEnv for LoggingEnv {
+    ///     fn implicit_close(&mut self, tag: &str) {
+    ///         self.implicitly_closed.push(tag.to_owned());
+    ///     }
+    /// }
+    ///
+    /// let mut env = LoggingEnv::default();
+    /// let result = env.write_html(html!(div { (TryHtml(Err::<Empty, _>("oops"))) }));
+    /// assert!(result.is_err());
+    /// assert_eq!(env.html, "<div></div>");
+    /// assert_eq!(env.implicitly_closed, ["div"]);
+    /// ```
+    fn implicit_close(&mut self, _tag: &str) {}
+
+    /// Returns the assets collected from the components, see [`with_assets`].
+    ///
+    /// This is `None` by default, in which case the declared assets are ignored.
+    fn assets(&mut self) -> Option<&mut Assets> {
+        None
+    }
+
+    /// Returns the value of the context of the given type, see [`HtmlEnv::context`].
+    ///
+    /// This is `None` by default. It is the object-safe part of [`HtmlEnv::context`], which
+    /// environments that wrap another one forward.
+    fn context_value(&self, _type_id: TypeId) -> Option<&dyn Any> {
+        None
+    }
+
+    /// Returns the value of type `T` of the context, given by the nearest enclosing
+    /// [`with_context`].
+    ///
+    /// The value borrows the environment, so clone what you need before writing.
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    ///
+    /// /// The nonce of the Content Security Policy of the response.
+    /// struct Nonce(String);
+    ///
+    /// struct InlineScript(&'static str);
+    ///
+    /// impl Html for InlineScript {
+    ///     fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+    ///         let nonce = env.context::<Nonce>().map(|n| n.0.clone());
+    ///         let mut opening = env.open_tag("script", Compactability::No)?;
+    ///         if let Some(nonce) = nonce {
+    ///             opening.attr("nonce", &nonce)?;
+    ///         }
+    ///         opening.inner_html()?.write_html(HtmlStr(self.0))?;
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let page = with_context(
+    ///     ContextMap::new().with(Nonce("r4nd0m".to_owned())),
+    ///     InlineScript("start()"),
+    /// );
+    /// assert_eq!(page.to_html_string().unwrap(), "<script nonce=\"r4nd0m\">start()</script>");
+    ///
+    /// // without context
+    /// assert_eq!(InlineScript("start()").to_html_string().unwrap(), "<script>start()</script>");
+    /// ```
+    fn context<T: Any>(&self) -> Option<&T> {
+        self.context_value(TypeId::of::<T>())?.downcast_ref()
+    }
 }
 
-impl<W: Write> HtmlEnv for W {}
+impl HtmlEnv for String {}
 
-/// Writes the default HTML5 `<meta>` tags.
+impl<'a> HtmlEnv for std::fmt::Formatter<'a> {}
+
+impl<E: HtmlEnv> HtmlEnv for &mut E {
+    fn serialization(&self) -> Serialization {
+        (**self).serialization()
+    }
+    fn before_open_tag(&mut self, tag: &str) -> Result<(), Error> {
+        (**self).before_open_tag(tag)
+    }
+    fn after_open_tag(&mut self, tag: &str) -> Result<(), Error> {
+        (**self).after_open_tag(tag)
+    }
+    fn before_close_tag(&mut self, tag: &str) -> Result<(), Error> {
+        (**self).before_close_tag(tag)
+    }
+    fn after_close_tag(&mut self, tag: &str) -> Result<(), Error> {
+        (**self).after_close_tag(tag)
+    }
+    fn assets(&mut self) -> Option<&mut Assets> {
+        (**self).assets()
+    }
+    fn implicit_close(&mut self, tag: &str) {
+        (**self).implicit_close(tag)
+    }
+    fn context_value(&self, type_id: TypeId) -> Option<&dyn Any> {
+        (**self).context_value(type_id)
+    }
+}
+
+/// Writes the default HTML5 `<meta>` tags, the charset and the viewport.
+#[deprecated(note = "use `Head`, which writes the same tags and lets you add the others")]
 pub struct DefaultMeta;
+#[allow(deprecated)]
 impl Html for DefaultMeta {
-    fn write_html(self, env: &mut impl HtmlEnv) -> std::fmt::Result {
-        env
-            .write_html(tags::meta(
-                [
-                    ("http-equiv", "X-UA-Compatible"),
-                    ("content", "ie=edge")
-                ],
-                Empty,
-            ))?
-            .write_html(tags::meta(
-                [("charset", "UTF-8")],
-                Empty,
-            ))?
-            .write_html(tags::meta(
-                [
-                    ("name", "viewport"),
-                    ("content", "width=device-width, initial-scale=1.0")
-                ],
-                Empty,
-            )).map(|_| ())
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        Head::new().write_html(env)
     }
 }
 
-/// Writes the HTML5 doctype.
+/// Writes the doctype of the environment, see [`HtmlEnv::doctype`].
 pub struct Doctype;
 impl Html for Doctype {
-    fn write_html(self, env: &mut impl HtmlEnv) -> std::fmt::Result {
-        env.doctype();
-        Ok(())
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (15, Some(38))
+    }
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        env.doctype()
     }
 }
\ No newline at end of file
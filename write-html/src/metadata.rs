@@ -0,0 +1,879 @@
+/*!
+Provides the metadata for social previews and search engines: [Open Graph](https://ogp.me/)
+([`OpenGraph`]), [Twitter cards](https://developer.x.com/en/docs/x-for-websites/cards/overview/markup)
+([`TwitterCard`]) and [schema.org](https://schema.org/) structured data in JSON-LD
+([`Article`], [`BreadcrumbList`], [`Organization`]).
+
+They are written with the tags of [`tags`](crate::tags), and go in the `<head>` of the page.
+
+# Example
+```
+use write_html::*;
+use write_html::metadata::{OpenGraph, OgImage, TwitterCard, Article};
+use write_html::feed::Person;
+
+let published = DateTime::from_ymd_hms(2024, 3, 1, 9, 0, 0).unwrap();
+let page = html!(
+    head {
+        (OpenGraph::article("Hello", "https://example.com/hello")
+            .with_image(OgImage::new("https://example.com/hello.png").with_size(1200, 630))
+            .with_published_time(published))
+        (TwitterCard::summary_large_image().with_site("@example"))
+        (Article::new("Hello")
+            .with_author(Person::new("Ada").with_uri("https://example.com/ada"))
+            .with_date_published(published))
+    }
+);
+assert_eq!(page.to_html_string().unwrap(), concat!(
+    "<head>",
+    "<meta property=\"og:type\" content=\"article\">",
+    "<meta property=\"og:title\" content=\"Hello\">",
+    "<meta property=\"og:url\" content=\"https://example.com/hello\">",
+    "<meta property=\"og:image\" content=\"https://example.com/hello.png\">",
+    "<meta property=\"og:image:width\" content=\"1200\">",
+    "<meta property=\"og:image:height\" content=\"630\">",
+    "<meta property=\"article:published_time\" content=\"2024-03-01T09:00:00Z\">",
+    "<meta name=\"twitter:card\" content=\"summary_large_image\">",
+    "<meta name=\"twitter:site\" content=\"@example\">",
+    "<script type=\"application/ld+json\">",
+    "{\"@context\":\"https://schema.org\",\"@type\":\"Article\",\"headline\":\"Hello\",",
+    "\"datePublished\":\"2024-03-01T09:00:00Z\",",
+    "\"author\":[{\"@type\":\"Person\",\"name\":\"Ada\",\"url\":\"https://example.com/ada\"}]}",
+    "</script>",
+    "</head>",
+));
+```
+*/
+
+use std::fmt::{Display, Write};
+
+use crate::{attribute_value_from_fn, feed::Person, tags, DateTime, Empty, Error, Html, HtmlEnv, HtmlStr};
+
+
+/// A JSON value, see [`JsonLd`].
+///
+/// It is written as compact JSON by its [`Display`] implementation.
+///
+/// # Example
+/// ```
+/// use write_html::metadata::Json;
+///
+/// let json = Json::object([
+///     ("name", Json::from("a \"quoted\" name")),
+///     ("tags", Json::Array(vec![Json::from(1), Json::Null, Json::from(true)])),
+/// ]);
+/// assert_eq!(json.to_string(), r#"{"name":"a \"quoted\" name","tags":[1,null,true]}"#);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    /// A number, written as `null` if it is not finite.
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    /// An object, whose members are written in order.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Creates an object with the given members.
+    pub fn object<K: Into<String>>(members: impl IntoIterator<Item = (K, Json)>) -> Self {
+        Json::Object(members.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    /// Adds a member to an object, if `value` is not `None`.
+    fn push(&mut self, key: &str, value: impl Into<Option<Json>>) {
+        if let (Json::Object(members), Some(value)) = (self, value.into()) {
+            members.push((key.to_owned(), value));
+        }
+    }
+}
+
+impl From<bool> for Json {
+    fn from(b: bool) -> Self {
+        Json::Bool(b)
+    }
+}
+
+impl From<f64> for Json {
+    fn from(n: f64) -> Self {
+        Json::Number(n)
+    }
+}
+
+impl From<i32> for Json {
+    fn from(n: i32) -> Self {
+        Json::Number(n.into())
+    }
+}
+
+impl From<u32> for Json {
+    fn from(n: u32) -> Self {
+        Json::Number(n.into())
+    }
+}
+
+impl From<&str> for Json {
+    fn from(s: &str) -> Self {
+        Json::String(s.to_owned())
+    }
+}
+
+impl From<String> for Json {
+    fn from(s: String) -> Self {
+        Json::String(s)
+    }
+}
+
+impl From<DateTime> for Json {
+    fn from(date: DateTime) -> Self {
+        Json::String(date.rfc3339().to_string())
+    }
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(items: Vec<T>) -> Self {
+        Json::Array(items.into_iter().map(Into::into).collect())
+    }
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Number(n) if n.is_finite() => write!(f, "{}", n),
+            Json::Number(_) => f.write_str("null"),
+            Json::String(s) => write_json_string(f, s),
+            Json::Array(items) => {
+                f.write_char('[')?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_char(']')
+            }
+            Json::Object(members) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_json_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+/// Writes a JSON string literal.
+///
+/// Besides the characters that JSON requires to escape, `<`, `>` and `&` are escaped, so that
+/// the JSON can be embedded in a `<script>` (it can't contain `</script>` nor `<!--`).
+fn write_json_string(w: &mut impl Write, s: &str) -> std::fmt::Result {
+    w.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => w.write_str("\\\"")?,
+            '\\' => w.write_str("\\\\")?,
+            '\n' => w.write_str("\\n")?,
+            '\r' => w.write_str("\\r")?,
+            '\t' => w.write_str("\\t")?,
+            '<' | '>' | '&' | '\u{2028}' | '\u{2029}' => write!(w, "\\u{:04x}", c as u32)?,
+            c if c < ' ' => write!(w, "\\u{:04x}", c as u32)?,
+            c => w.write_char(c)?,
+        }
+    }
+    w.write_char('"')
+}
+
+/// Structured data, written as `<script type="application/ld+json">`.
+///
+/// The JSON is escaped so that any text can be embedded in the script.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+/// use write_html::metadata::{Json, JsonLd};
+///
+/// let json_ld = JsonLd(Json::object([("name", Json::from("</script><script>alert(1)"))]));
+/// assert_eq!(
+///     json_ld.to_html_string().unwrap(),
+///     r#"<script type="application/ld+json">{"name":"\u003c/script\u003e\u003cscript\u003ealert(1)"}</script>"#
+/// );
+/// ```
+pub struct JsonLd(pub Json);
+
+impl Html for JsonLd {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        env.write_html(tags::script([("type", "application/ld+json")], HtmlStr(self.0.to_string())))?;
+        Ok(())
+    }
+}
+
+/// Creates a schema.org object of the given type.
+fn schema(schema_type: &str) -> Json {
+    Json::object([("@context", Json::from("https://schema.org")), ("@type", Json::from(schema_type))])
+}
+
+/// Returns the schema.org `Person` of `person`.
+fn person_json(person: &Person) -> Json {
+    let mut json = Json::object([("@type", Json::from("Person")), ("name", Json::from(person.name.as_str()))]);
+    json.push("url", person.uri.as_deref().map(Json::from));
+    json.push("email", person.email.as_deref().map(Json::from));
+    json
+}
+
+/// A schema.org [`Organization`](https://schema.org/Organization), like the publisher of a site.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+/// use write_html::metadata::Organization;
+///
+/// let organization = Organization::new("Example")
+///     .with_url("https://example.com/")
+///     .with_logo("https://example.com/logo.png")
+///     .with_same_as("https://github.com/example");
+/// assert_eq!(organization.to_html_string().unwrap(), concat!(
+///     "<script type=\"application/ld+json\">",
+///     "{\"@context\":\"https://schema.org\",\"@type\":\"Organization\",\"name\":\"Example\",",
+///     "\"url\":\"https://example.com/\",\"logo\":\"https://example.com/logo.png\",",
+///     "\"sameAs\":[\"https://github.com/example\"]}",
+///     "</script>",
+/// ));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Organization {
+    name: String,
+    url: Option<String>,
+    logo: Option<String>,
+    same_as: Vec<String>,
+}
+
+impl Organization {
+    /// Creates a new `Organization` with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), url: None, logo: None, same_as: Vec::new() }
+    }
+
+    /// Sets the URL of the website.
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Sets the URL of the logo.
+    pub fn with_logo(mut self, logo: impl Into<String>) -> Self {
+        self.logo = Some(logo.into());
+        self
+    }
+
+    /// Adds the URL of a page about the organization on another site, like a social network.
+    pub fn with_same_as(mut self, url: impl Into<String>) -> Self {
+        self.same_as.push(url.into());
+        self
+    }
+
+    /// Returns the schema.org object, without `@context`.
+    fn to_json(&self) -> Json {
+        Json::Object(self.members())
+    }
+
+    /// Returns the members of the schema.org object, from its `@type`.
+    fn members(&self) -> Vec<(String, Json)> {
+        let mut members = vec![
+            ("@type".to_owned(), Json::from("Organization")),
+            ("name".to_owned(), Json::from(self.name.as_str())),
+        ];
+        if let Some(url) = &self.url {
+            members.push(("url".to_owned(), Json::from(url.as_str())));
+        }
+        if let Some(logo) = &self.logo {
+            members.push(("logo".to_owned(), Json::from(logo.as_str())));
+        }
+        if !self.same_as.is_empty() {
+            members.push(("sameAs".to_owned(), Json::from(self.same_as.clone())));
+        }
+        members
+    }
+}
+
+impl From<Organization> for Json {
+    fn from(organization: Organization) -> Self {
+        let mut members = vec![("@context".to_owned(), Json::from("https://schema.org"))];
+        members.extend(organization.members());
+        Json::Object(members)
+    }
+}
+
+impl Html for Organization {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        JsonLd(self.into()).write_html(env)
+    }
+}
+
+/// A schema.org [`Article`](https://schema.org/Article) or
+/// [`ScholarlyArticle`](https://schema.org/ScholarlyArticle).
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Article {
+    schema_type: &'static str,
+    headline: String,
+    url: Option<String>,
+    description: Option<String>,
+    images: Vec<String>,
+    date_published: Option<DateTime>,
+    date_modified: Option<DateTime>,
+    authors: Vec<Person>,
+    publisher: Option<Organization>,
+    identifier: Option<String>,
+    citations: Vec<String>,
+}
+
+impl Article {
+    /// Creates a new `Article` with the given headline.
+    pub fn new(headline: impl Into<String>) -> Self {
+        Self {
+            schema_type: "Article",
+            headline: headline.into(),
+            url: None,
+            description: None,
+            images: Vec::new(),
+            date_published: None,
+            date_modified: None,
+            authors: Vec::new(),
+            publisher: None,
+            identifier: None,
+            citations: Vec::new(),
+        }
+    }
+
+    /// Creates a new `ScholarlyArticle`, like a paper, with the given headline.
+    pub fn scholarly(headline: impl Into<String>) -> Self {
+        Self { schema_type: "ScholarlyArticle", ..Self::new(headline) }
+    }
+
+    /// Sets the URL of the article.
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Sets the description, or the abstract of a scholarly article.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Adds the URL of an image.
+    pub fn with_image(mut self, image: impl Into<String>) -> Self {
+        self.images.push(image.into());
+        self
+    }
+
+    /// Sets the publication date.
+    pub fn with_date_published(mut self, date: DateTime) -> Self {
+        self.date_published = Some(date);
+        self
+    }
+
+    /// Sets the date of the last modification.
+    pub fn with_date_modified(mut self, date: DateTime) -> Self {
+        self.date_modified = Some(date);
+        self
+    }
+
+    /// Adds an author.
+    pub fn with_author(mut self, author: Person) -> Self {
+        self.authors.push(author);
+        self
+    }
+
+    /// Sets the publisher.
+    pub fn with_publisher(mut self, publisher: Organization) -> Self {
+        self.publisher = Some(publisher);
+        self
+    }
+
+    /// Sets an identifier, like the DOI of a scholarly article.
+    pub fn with_identifier(mut self, identifier: impl Into<String>) -> Self {
+        self.identifier = Some(identifier.into());
+        self
+    }
+
+    /// Adds a cited work, by its title or its URL.
+    pub fn with_citation(mut self, citation: impl Into<String>) -> Self {
+        self.citations.push(citation.into());
+        self
+    }
+}
+
+impl From<Article> for Json {
+    fn from(article: Article) -> Self {
+        let mut json = schema(article.schema_type);
+        json.push("headline", Json::from(article.headline));
+        json.push("url", article.url.map(Json::from));
+        json.push("description", article.description.map(Json::from));
+        if !article.images.is_empty() {
+            json.push("image", Json::from(article.images));
+        }
+        json.push("datePublished", article.date_published.map(Json::from));
+        json.push("dateModified", article.date_modified.map(Json::from));
+        if !article.authors.is_empty() {
+            json.push("author", Json::Array(article.authors.iter().map(person_json).collect()));
+        }
+        json.push("publisher", article.publisher.as_ref().map(Organization::to_json));
+        json.push("identifier", article.identifier.map(Json::from));
+        if !article.citations.is_empty() {
+            json.push("citation", Json::from(article.citations));
+        }
+        json
+    }
+}
+
+impl Html for Article {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        JsonLd(self.into()).write_html(env)
+    }
+}
+
+/// A schema.org [`BreadcrumbList`](https://schema.org/BreadcrumbList), the position of a page
+/// in the hierarchy of the site.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+/// use write_html::metadata::BreadcrumbList;
+///
+/// let breadcrumbs = BreadcrumbList::new()
+///     .with_item("Home", "https://example.com/")
+///     .with_item("Blog", "https://example.com/blog");
+/// assert_eq!(breadcrumbs.to_html_string().unwrap(), concat!(
+///     "<script type=\"application/ld+json\">",
+///     "{\"@context\":\"https://schema.org\",\"@type\":\"BreadcrumbList\",\"itemListElement\":[",
+///     "{\"@type\":\"ListItem\",\"position\":1,\"name\":\"Home\",\"item\":\"https://example.com/\"},",
+///     "{\"@type\":\"ListItem\",\"position\":2,\"name\":\"Blog\",\"item\":\"https://example.com/blog\"}",
+///     "]}</script>",
+/// ));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BreadcrumbList {
+    items: Vec<(String, String)>,
+}
+
+impl BreadcrumbList {
+    /// Creates an empty `BreadcrumbList`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a page, after the previous ones.
+    pub fn with_item(mut self, name: impl Into<String>, url: impl Into<String>) -> Self {
+        self.items.push((name.into(), url.into()));
+        self
+    }
+}
+
+impl From<BreadcrumbList> for Json {
+    fn from(list: BreadcrumbList) -> Self {
+        let items = list.items.into_iter().zip(1..).map(|((name, url), position)| Json::object([
+            ("@type", Json::from("ListItem")),
+            ("position", Json::from(position)),
+            ("name", Json::from(name)),
+            ("item", Json::from(url)),
+        ]));
+        let mut json = schema("BreadcrumbList");
+        json.push("itemListElement", Json::Array(items.collect()));
+        json
+    }
+}
+
+impl Html for BreadcrumbList {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        JsonLd(self.into()).write_html(env)
+    }
+}
+
+/// An image of [`OpenGraph`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OgImage {
+    url: String,
+    size: Option<(u32, u32)>,
+    alt: Option<String>,
+    mime_type: Option<String>,
+}
+
+impl OgImage {
+    /// Creates a new `OgImage` with the given URL.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into(), size: None, alt: None, mime_type: None }
+    }
+
+    /// Sets the width and the height, in pixels.
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    /// Sets the description of the image.
+    pub fn with_alt(mut self, alt: impl Into<String>) -> Self {
+        self.alt = Some(alt.into());
+        self
+    }
+
+    /// Sets the MIME type, like `image/png`.
+    pub fn with_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+}
+
+/// The type of an [`OpenGraph`] object, with its specific properties.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum OgType {
+    Website,
+    Article {
+        published_time: Option<DateTime>,
+        modified_time: Option<DateTime>,
+        authors: Vec<String>,
+        section: Option<String>,
+        tags: Vec<String>,
+    },
+    Profile {
+        first_name: Option<String>,
+        last_name: Option<String>,
+        username: Option<String>,
+    },
+}
+
+/// The [Open Graph](https://ogp.me/) metadata of a page, written as `<meta property="og:...">` tags.
+///
+/// The properties of another type than the one of the object are ignored (like
+/// [`with_published_time`](OpenGraph::with_published_time) for a website).
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenGraph {
+    og_type: OgType,
+    title: String,
+    url: String,
+    description: Option<String>,
+    site_name: Option<String>,
+    locale: Option<String>,
+    images: Vec<OgImage>,
+}
+
+impl OpenGraph {
+    fn new(og_type: OgType, title: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            og_type,
+            title: title.into(),
+            url: url.into(),
+            description: None,
+            site_name: None,
+            locale: None,
+            images: Vec::new(),
+        }
+    }
+
+    /// Creates the metadata of a website (`og:type` is `website`).
+    pub fn website(title: impl Into<String>, url: impl Into<String>) -> Self {
+        Self::new(OgType::Website, title, url)
+    }
+
+    /// Creates the metadata of an article (`og:type` is `article`).
+    pub fn article(title: impl Into<String>, url: impl Into<String>) -> Self {
+        let og_type = OgType::Article {
+            published_time: None,
+            modified_time: None,
+            authors: Vec::new(),
+            section: None,
+            tags: Vec::new(),
+        };
+        Self::new(og_type, title, url)
+    }
+
+    /// Creates the metadata of the profile of a person (`og:type` is `profile`).
+    pub fn profile(title: impl Into<String>, url: impl Into<String>) -> Self {
+        let og_type = OgType::Profile { first_name: None, last_name: None, username: None };
+        Self::new(og_type, title, url)
+    }
+
+    /// Sets the description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the name of the site.
+    pub fn with_site_name(mut self, site_name: impl Into<String>) -> Self {
+        self.site_name = Some(site_name.into());
+        self
+    }
+
+    /// Sets the locale, like `en_US`.
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Adds an image.
+    pub fn with_image(mut self, image: OgImage) -> Self {
+        self.images.push(image);
+        self
+    }
+
+    /// Sets the publication time of an article.
+    pub fn with_published_time(mut self, time: DateTime) -> Self {
+        if let OgType::Article { published_time, .. } = &mut self.og_type {
+            *published_time = Some(time);
+        }
+        self
+    }
+
+    /// Sets the time of the last modification of an article.
+    pub fn with_modified_time(mut self, time: DateTime) -> Self {
+        if let OgType::Article { modified_time, .. } = &mut self.og_type {
+            *modified_time = Some(time);
+        }
+        self
+    }
+
+    /// Adds the URL of the profile of an author of an article.
+    pub fn with_author(mut self, profile_url: impl Into<String>) -> Self {
+        if let OgType::Article { authors, .. } = &mut self.og_type {
+            authors.push(profile_url.into());
+        }
+        self
+    }
+
+    /// Sets the section of an article, like `Technology`.
+    pub fn with_section(mut self, name: impl Into<String>) -> Self {
+        if let OgType::Article { section, .. } = &mut self.og_type {
+            *section = Some(name.into());
+        }
+        self
+    }
+
+    /// Adds a tag of an article.
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        if let OgType::Article { tags, .. } = &mut self.og_type {
+            tags.push(tag.into());
+        }
+        self
+    }
+
+    /// Sets the first name of a profile.
+    pub fn with_first_name(mut self, name: impl Into<String>) -> Self {
+        if let OgType::Profile { first_name, .. } = &mut self.og_type {
+            *first_name = Some(name.into());
+        }
+        self
+    }
+
+    /// Sets the last name of a profile.
+    pub fn with_last_name(mut self, name: impl Into<String>) -> Self {
+        if let OgType::Profile { last_name, .. } = &mut self.og_type {
+            *last_name = Some(name.into());
+        }
+        self
+    }
+
+    /// Sets the username of a profile.
+    pub fn with_username(mut self, name: impl Into<String>) -> Self {
+        if let OgType::Profile { username, .. } = &mut self.og_type {
+            *username = Some(name.into());
+        }
+        self
+    }
+}
+
+impl Html for OpenGraph {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        let og_type = match self.og_type {
+            OgType::Website => "website",
+            OgType::Article { .. } => "article",
+            OgType::Profile { .. } => "profile",
+        };
+        write_property(env, "og:type", og_type)?;
+        write_property(env, "og:title", &self.title)?;
+        write_property(env, "og:url", &self.url)?;
+        if let Some(description) = &self.description {
+            write_property(env, "og:description", description)?;
+        }
+        if let Some(site_name) = &self.site_name {
+            write_property(env, "og:site_name", site_name)?;
+        }
+        if let Some(locale) = &self.locale {
+            write_property(env, "og:locale", locale)?;
+        }
+        for image in &self.images {
+            // the structured properties follow the image they describe
+            write_property(env, "og:image", &image.url)?;
+            if let Some(mime_type) = &image.mime_type {
+                write_property(env, "og:image:type", mime_type)?;
+            }
+            if let Some((width, height)) = image.size {
+                write_property(env, "og:image:width", width)?;
+                write_property(env, "og:image:height", height)?;
+            }
+            if let Some(alt) = &image.alt {
+                write_property(env, "og:image:alt", alt)?;
+            }
+        }
+
+        match &self.og_type {
+            OgType::Website => {}
+            OgType::Article { published_time, modified_time, authors, section, tags } => {
+                if let Some(time) = published_time {
+                    write_property(env, "article:published_time", time.rfc3339())?;
+                }
+                if let Some(time) = modified_time {
+                    write_property(env, "article:modified_time", time.rfc3339())?;
+                }
+                for author in authors {
+                    write_property(env, "article:author", author)?;
+                }
+                if let Some(section) = section {
+                    write_property(env, "article:section", section)?;
+                }
+                for tag in tags {
+                    write_property(env, "article:tag", tag)?;
+                }
+            }
+            OgType::Profile { first_name, last_name, username } => {
+                if let Some(name) = first_name {
+                    write_property(env, "profile:first_name", name)?;
+                }
+                if let Some(name) = last_name {
+                    write_property(env, "profile:last_name", name)?;
+                }
+                if let Some(name) = username {
+                    write_property(env, "profile:username", name)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes a `<meta property="..." content="...">`.
+fn write_property(env: &mut impl HtmlEnv, property: &str, content: impl Display) -> Result<(), Error> {
+    let content = attribute_value_from_fn(|w| write!(w, "{}", content));
+    env.write_html(tags::meta(Empty, Empty).attr("property", property).attr("content", content))?;
+    Ok(())
+}
+
+/// A [Twitter card](https://developer.x.com/en/docs/x-for-websites/cards/overview/markup),
+/// written as `<meta name="twitter:...">` tags.
+///
+/// When the title, the description or the image are not set, the crawlers use the ones of
+/// [`OpenGraph`].
+///
+/// # Example
+/// ```
+/// use write_html::*;
+/// use write_html::metadata::TwitterCard;
+///
+/// let card = TwitterCard::summary()
+///     .with_creator("@ada")
+///     .with_image("https://example.com/a.png", "A diagram");
+/// assert_eq!(card.to_html_string().unwrap(), concat!(
+///     "<meta name=\"twitter:card\" content=\"summary\">",
+///     "<meta name=\"twitter:creator\" content=\"@ada\">",
+///     "<meta name=\"twitter:image\" content=\"https://example.com/a.png\">",
+///     "<meta name=\"twitter:image:alt\" content=\"A diagram\">",
+/// ));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TwitterCard {
+    card: &'static str,
+    site: Option<String>,
+    creator: Option<String>,
+    title: Option<String>,
+    description: Option<String>,
+    image: Option<(String, Option<String>)>,
+}
+
+impl TwitterCard {
+    fn new(card: &'static str) -> Self {
+        Self { card, site: None, creator: None, title: None, description: None, image: None }
+    }
+
+    /// Creates a card with a small square image.
+    pub fn summary() -> Self {
+        Self::new("summary")
+    }
+
+    /// Creates a card with a large image.
+    pub fn summary_large_image() -> Self {
+        Self::new("summary_large_image")
+    }
+
+    /// Sets the account of the site, like `@example`.
+    pub fn with_site(mut self, site: impl Into<String>) -> Self {
+        self.site = Some(site.into());
+        self
+    }
+
+    /// Sets the account of the author, like `@ada`.
+    pub fn with_creator(mut self, creator: impl Into<String>) -> Self {
+        self.creator = Some(creator.into());
+        self
+    }
+
+    /// Sets the title.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the URL of the image, with its description if any (use `""` for none).
+    pub fn with_image(mut self, url: impl Into<String>, alt: impl Into<String>) -> Self {
+        let alt = alt.into();
+        self.image = Some((url.into(), Some(alt).filter(|a| !a.is_empty())));
+        self
+    }
+}
+
+impl Html for TwitterCard {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        let mut write = |name: &str, content: &str| -> Result<(), Error> {
+            env.write_html(tags::meta([("name", name), ("content", content)], Empty))?;
+            Ok(())
+        };
+        write("twitter:card", self.card)?;
+        if let Some(site) = &self.site {
+            write("twitter:site", site)?;
+        }
+        if let Some(creator) = &self.creator {
+            write("twitter:creator", creator)?;
+        }
+        if let Some(title) = &self.title {
+            write("twitter:title", title)?;
+        }
+        if let Some(description) = &self.description {
+            write("twitter:description", description)?;
+        }
+        if let Some((url, alt)) = &self.image {
+            write("twitter:image", url)?;
+            if let Some(alt) = alt {
+                write("twitter:image:alt", alt)?;
+            }
+        }
+        Ok(())
+    }
+}
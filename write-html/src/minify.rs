@@ -0,0 +1,433 @@
+use std::any::{Any, TypeId};
+use std::fmt::Write;
+
+use crate::{Assets, HtmlEnv, Error, Serialization, is_void_element};
+
+
+/// Elements whose text is written unchanged.
+const WHITESPACE_SENSITIVE_ELEMENTS: &[&str] = &["pre", "textarea", "script", "style"];
+
+/// Attributes whose value is irrelevant, only their presence matters.
+const BOOLEAN_ATTRIBUTES: &[&str] = &[
+    "allowfullscreen", "async", "autofocus", "autoplay", "checked", "controls", "default", "defer",
+    "disabled", "formnovalidate", "hidden", "inert", "ismap", "itemscope", "loop", "multiple", "muted",
+    "nomodule", "novalidate", "open", "playsinline", "readonly", "required", "reversed", "selected",
+];
+
+/// Elements that close an open `<p>`, see [`can_omit_closing_tag`].
+const P_CLOSING_ELEMENTS: &[&str] = &[
+    "address", "article", "aside", "blockquote", "details", "dialog", "div", "dl", "fieldset",
+    "figcaption", "figure", "footer", "form", "h1", "h2", "h3", "h4", "h5", "h6", "header", "hgroup",
+    "hr", "main", "menu", "nav", "ol", "p", "pre", "search", "section", "table", "ul",
+];
+
+/// What follows an optional tag.
+#[derive(Clone, Copy)]
+enum Next<'a> {
+    /// The opening of an element.
+    Element(&'a str),
+    /// The end of the parent element.
+    ParentEnd(&'a str),
+    /// Some text.
+    Text,
+    /// The end of the document.
+    End,
+}
+
+/// Tells whether the closing tag of `tag` may be omitted, according to
+/// [the HTML standard](https://html.spec.whatwg.org/multipage/syntax.html#optional-tags).
+fn can_omit_closing_tag(tag: &str, next: Next) -> bool {
+    use Next::*;
+    match (tag, next) {
+        (_, Text) => false,
+        ("html" | "body" | "head", _) => true,
+        (_, End) => false,
+        ("li", Element("li") | ParentEnd(_)) => true,
+        ("dt", Element("dt" | "dd")) => true,
+        ("dd", Element("dt" | "dd") | ParentEnd(_)) => true,
+        ("p", Element(next)) => P_CLOSING_ELEMENTS.contains(&next),
+        ("p", ParentEnd(parent)) => !matches!(parent, "a" | "audio" | "del" | "ins" | "map" | "noscript" | "video"),
+        ("rt" | "rp", Element("rt" | "rp") | ParentEnd(_)) => true,
+        ("optgroup", Element("optgroup" | "hr") | ParentEnd(_)) => true,
+        ("option", Element("option" | "optgroup" | "hr") | ParentEnd(_)) => true,
+        ("thead", Element("tbody" | "tfoot")) => true,
+        ("tbody", Element("tbody" | "tfoot") | ParentEnd(_)) => true,
+        ("tfoot", ParentEnd(_)) => true,
+        ("tr", Element("tr") | ParentEnd(_)) => true,
+        ("td" | "th", Element("td" | "th") | ParentEnd(_)) => true,
+        _ => false,
+    }
+}
+
+/// Tells whether the opening tag of `tag`, without attributes, may be omitted.
+fn can_omit_opening_tag(tag: &str, next: Next) -> bool {
+    use Next::*;
+    match (tag, next) {
+        ("html" | "head" | "body", ParentEnd(_)) => true,
+        ("html" | "head", Element(_)) => true,
+        ("body", Element(next)) => !matches!(next, "meta" | "noscript" | "link" | "script" | "style" | "template"),
+        _ => false,
+    }
+}
+
+/// An [`HtmlEnv`] that minifies the written HTML.
+///
+/// The output is parse-equivalent to the original one:
+/// * runs of whitespace in text are collapsed into a single space (except inside `pre`,
+///   `textarea`, `script` and `style`),
+/// * optional opening and closing tags (like `<head>`, `</li>`, `</p>` or `</td>`) are omitted,
+/// * quotes around attribute values are dropped when they are not needed,
+/// * boolean attributes are written without value, and void elements without final slash.
+///
+/// If the [serialization](HtmlEnv::serialization) of `w` is not HTML, only the whitespace in
+/// text is collapsed, since the other changes are not valid in XML.
+///
+/// Raw HTML (like [`HtmlStr`](crate::HtmlStr) or [`Prerendered`](crate::Prerendered)) is
+/// minified like text, except that its tags, and the content of its `pre`, `textarea`,
+/// `script` and `style` elements, are written unchanged.
+/// The last closing tags are written by [`MinifyEnv::finish`], or when the `MinifyEnv` is
+/// dropped (see [`MinifyEnv::new`]).
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let mut env = MinifyEnv::new(String::new());
+/// env.write_html(html!(
+///     ul .list {
+///         li { "Item   1" }
+///         li { input type="checkbox" checked="checked"; }
+///     }
+///     p { "Paragraph" }
+/// )).unwrap();
+/// assert_eq!(
+///     env.finish().unwrap(),
+///     "<ul class=list><li>Item 1<li><input type=checkbox checked></ul><p>Paragraph</p>"
+/// );
+///
+/// // `<body>` is kept when it is needed to parse its content in the body
+/// let mut env = MinifyEnv::new(String::new());
+/// env.write_html(html!(html { head {} body { noscript { "No JavaScript" } } })).unwrap();
+/// assert_eq!(env.finish().unwrap(), "<body><noscript>No JavaScript</noscript>");
+///
+/// // prerendered HTML keeps its whitespace-sensitive content, like the same HTML written live
+/// let code = Prerendered::new(html!(pre { "a\n    b" })).unwrap();
+/// let mut env = MinifyEnv::new(String::new());
+/// env.write_html(html!(div { "x   y" (&code) (HtmlStr("<p  class=\"a  b\">c   d</p>")) })).unwrap();
+/// assert_eq!(env.finish().unwrap(), "<div>x y<pre>a<br>    b</pre><p  class=\"a  b\">c d</p></div>");
+///
+/// let mut live = MinifyEnv::new(String::new());
+/// live.write_html(html!(div { "x   y" pre { "a\n    b" } })).unwrap();
+/// assert_eq!(live.finish().unwrap(), "<div>x y<pre>a<br>    b</pre></div>");
+/// ```
+pub struct MinifyEnv<W: HtmlEnv> {
+    /// The underlying environment, always `Some` until [`MinifyEnv::finish`].
+    w: Option<W>,
+    /// The opening of the current tag, until it is finished.
+    opening: Option<String>,
+    /// Whether the closing of a tag is being written, which is skipped and written again if needed.
+    closing: bool,
+    /// An opening tag that may be omitted, depending on what follows.
+    pending_opening: Option<(String, String)>,
+    /// A closing tag that may be omitted, depending on what follows.
+    pending_closing: Option<String>,
+    /// The number of open whitespace-sensitive elements.
+    whitespace_sensitive: usize,
+    /// Whether a tag written as raw HTML is being written, which is written unchanged.
+    in_raw_tag: bool,
+    /// The whitespace-sensitive element opened in raw HTML, whose content is written unchanged.
+    raw_element: Option<String>,
+    /// Whether the last written text character is a whitespace.
+    after_whitespace: bool,
+}
+
+impl<W: HtmlEnv> MinifyEnv<W> {
+    /// Creates a new `MinifyEnv` that will write to `w`.
+    ///
+    /// Whether a closing tag can be omitted depends on what follows it, so the last ones are
+    /// only written at the end: by [`MinifyEnv::finish`], which returns `w`, or when the
+    /// `MinifyEnv` is dropped, which ignores the errors. When `w` is borrowed (like a
+    /// `&mut String`), the output is complete once the `MinifyEnv` is finished or dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    ///
+    /// let mut s = String::new();
+    /// {
+    ///     let mut env = MinifyEnv::new(&mut s);
+    ///     env.write_html(html!(div { p { "Hi" } })).unwrap();
+    /// }
+    /// assert_eq!(s, "<div><p>Hi</div>");
+    /// ```
+    pub fn new(w: W) -> Self {
+        Self {
+            w: Some(w),
+            opening: None,
+            closing: false,
+            pending_opening: None,
+            pending_closing: None,
+            whitespace_sensitive: 0,
+            in_raw_tag: false,
+            raw_element: None,
+            after_whitespace: false,
+        }
+    }
+
+    /// Writes the tags that are still pending and returns the underlying environment.
+    pub fn finish(mut self) -> Result<W, Error> {
+        self.resolve_pending(Next::End)?;
+        Ok(self.w.take().unwrap())
+    }
+
+    fn w(&mut self) -> &mut W {
+        self.w.as_mut().unwrap()
+    }
+
+    /// Whether the HTML syntax is used, so that tags can be omitted and openings minified.
+    fn is_html(&self) -> bool {
+        self.w.as_ref().unwrap().serialization() == Serialization::Html
+    }
+
+    /// Minifies the opening of a tag, if the HTML syntax is used.
+    fn minify_opening(&self, tag: &str, opening: String) -> String {
+        if !self.is_html() {
+            return opening;
+        }
+        minify_opening(tag, &opening).unwrap_or(opening)
+    }
+
+    /// Writes or omits the pending tags, depending on what follows them.
+    fn resolve_pending(&mut self, next: Next) -> Result<(), Error> {
+        if let Some((tag, opening)) = self.pending_opening.take() {
+            if !can_omit_opening_tag(&tag, next) {
+                self.w().write_str(&opening)?;
+            }
+        }
+        if let Some(tag) = self.pending_closing.take() {
+            if !self.is_html() || !can_omit_closing_tag(&tag, next) {
+                write!(self.w(), "</{}>", tag)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes text, which has no `<` once escaped, or raw HTML, whose tags are kept unchanged.
+    fn write_text(&mut self, s: &str) -> std::fmt::Result {
+        if self.whitespace_sensitive > 0 {
+            return self.w().write_str(s);
+        }
+
+        let mut rest = s;
+        while !rest.is_empty() {
+            if self.in_raw_tag {
+                let end = rest.find('>').map_or(rest.len(), |i| i + 1);
+                self.in_raw_tag = !rest[..end].ends_with('>');
+                self.w().write_str(&rest[..end])?;
+                rest = &rest[end..];
+            } else if let Some(element) = &self.raw_element {
+                let end = find_closing_tag(rest, element).unwrap_or(rest.len());
+                if end < rest.len() {
+                    self.raw_element = None;
+                }
+                self.w().write_str(&rest[..end])?;
+                rest = &rest[end..];
+            } else if rest.starts_with('<') {
+                let end = rest.find('>').map_or(rest.len(), |i| i + 1);
+                let tag = &rest[..end];
+                let name_len = tag[1..].find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(tag.len() - 1);
+                let name = tag[1..1 + name_len].to_ascii_lowercase();
+                if WHITESPACE_SENSITIVE_ELEMENTS.contains(&name.as_str()) && !tag.ends_with("/>") {
+                    self.raw_element = Some(name);
+                }
+                self.in_raw_tag = !tag.ends_with('>');
+                self.after_whitespace = false;
+                self.w().write_str(tag)?;
+                rest = &rest[end..];
+            } else {
+                let end = rest.find('<').unwrap_or(rest.len());
+                self.collapse_whitespace(&rest[..end])?;
+                rest = &rest[end..];
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes text with its runs of whitespace collapsed into a single space.
+    fn collapse_whitespace(&mut self, s: &str) -> std::fmt::Result {
+        let mut start = 0;
+        for (i, c) in s.char_indices() {
+            if !c.is_ascii_whitespace() {
+                self.after_whitespace = false;
+                continue;
+            }
+            self.w().write_str(&s[start..i])?;
+            if !self.after_whitespace {
+                self.w().write_str(" ")?;
+            }
+            self.after_whitespace = true;
+            start = i + c.len_utf8();
+        }
+        self.w().write_str(&s[start..])
+    }
+}
+
+impl<W: HtmlEnv> Write for MinifyEnv<W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        if self.closing {
+            return Ok(());
+        }
+        if let Some(opening) = &mut self.opening {
+            opening.push_str(s);
+            return Ok(());
+        }
+        if s.is_empty() {
+            return Ok(());
+        }
+        self.resolve_pending(Next::Text)?;
+        self.write_text(s)
+    }
+}
+
+impl<W: HtmlEnv> HtmlEnv for MinifyEnv<W> {
+    fn serialization(&self) -> Serialization {
+        self.w.as_ref().unwrap().serialization()
+    }
+
+    fn before_open_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.resolve_pending(Next::Element(tag))?;
+        self.opening = Some(String::new());
+        self.after_whitespace = false;
+        Ok(())
+    }
+
+    fn after_open_tag(&mut self, tag: &str) -> Result<(), Error> {
+        let opening = self.opening.take().unwrap_or_default();
+        let opening = self.minify_opening(tag, opening);
+
+        if WHITESPACE_SENSITIVE_ELEMENTS.contains(&tag) {
+            self.whitespace_sensitive += 1;
+        }
+
+        if self.is_html() && matches!(tag, "html" | "head" | "body") && opening == format!("<{}>", tag) {
+            self.pending_opening = Some((tag.to_owned(), opening));
+            return Ok(());
+        }
+        self.w().write_str(&opening)?;
+        Ok(())
+    }
+
+    fn before_close_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.resolve_pending(Next::ParentEnd(tag))?;
+        self.closing = true;
+        self.after_whitespace = false;
+        Ok(())
+    }
+
+    fn after_close_tag(&mut self, tag: &str) -> Result<(), Error> {
+        // compacted tag, without closing
+        if let Some(opening) = self.opening.take() {
+            let opening = self.minify_opening(tag, opening);
+            self.w().write_str(&opening)?;
+            return Ok(());
+        }
+
+        self.closing = false;
+        if WHITESPACE_SENSITIVE_ELEMENTS.contains(&tag) {
+            self.whitespace_sensitive = self.whitespace_sensitive.saturating_sub(1);
+        }
+
+        self.pending_closing = Some(tag.to_owned());
+        Ok(())
+    }
+
+    fn assets(&mut self) -> Option<&mut Assets> {
+        self.w().assets()
+    }
+    fn implicit_close(&mut self, tag: &str) {
+        self.w().implicit_close(tag)
+    }
+    fn context_value(&self, type_id: TypeId) -> Option<&dyn Any> {
+        self.w.as_ref().unwrap().context_value(type_id)
+    }
+}
+
+impl<W: HtmlEnv> Drop for MinifyEnv<W> {
+    fn drop(&mut self) {
+        if self.w.is_some() {
+            // there is no way to report the error here, use `finish` to get it
+            let _ = self.resolve_pending(Next::End);
+        }
+    }
+}
+
+/// Returns the position of the closing tag of `element` in `s`, ignoring the case.
+fn find_closing_tag(s: &str, element: &str) -> Option<usize> {
+    s.match_indices("</").map(|(i, _)| i).find(|&i| {
+        s[i + 2..].get(..element.len()).is_some_and(|name| name.eq_ignore_ascii_case(element))
+    })
+}
+
+/// Minifies the opening of a tag, as written by [`TagOpening`](crate::TagOpening).
+///
+/// Returns `None` if the opening can't be parsed.
+fn minify_opening(tag: &str, opening: &str) -> Option<String> {
+    let mut rest = opening.strip_prefix('<')?.strip_prefix(tag)?;
+
+    let mut attributes = Vec::new();
+    let end = loop {
+        if rest == ">" || rest == "/>" {
+            break rest;
+        }
+        rest = rest.strip_prefix(' ')?;
+
+        let name_len = rest.find(['=', ' ', '>', '/']).unwrap_or(rest.len());
+        let name = &rest[..name_len];
+        if name.is_empty() {
+            return None;
+        }
+        rest = &rest[name_len..];
+
+        let value = match rest.strip_prefix("=\"") {
+            Some(r) => {
+                // values have no quotes, see `AttributeEscaper`
+                let value_len = r.find('"')?;
+                rest = &r[value_len + 1..];
+                Some(&r[..value_len])
+            }
+            None => None,
+        };
+        attributes.push((name, value));
+    };
+
+    let void = is_void_element(tag);
+    let final_slash = end == "/>" && !void;
+
+    let mut s = String::with_capacity(opening.len());
+    s.push('<');
+    s.push_str(tag);
+    for (name, value) in attributes {
+        s.push(' ');
+        s.push_str(name);
+        let value = match value {
+            Some(value) => value,
+            None => continue,
+        };
+        if BOOLEAN_ATTRIBUTES.contains(&name) && (value.is_empty() || value.eq_ignore_ascii_case(name)) {
+            continue;
+        }
+        let needs_quotes = value.is_empty()
+            || value.contains(|c: char| c.is_ascii_whitespace() || "\"'=<>`".contains(c))
+            || (final_slash && value.ends_with('/'));
+        if needs_quotes {
+            s.push_str("=\"");
+            s.push_str(value);
+            s.push('"');
+        } else {
+            s.push('=');
+            s.push_str(value);
+        }
+    }
+    s.push_str(if final_slash { "/>" } else { ">" });
+    Some(s)
+}
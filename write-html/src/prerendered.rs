@@ -0,0 +1,170 @@
+use std::any::{Any, TypeId};
+use std::fmt::Write;
+use std::sync::{Arc, OnceLock};
+
+use crate::{Assets, Html, HtmlEnv, ToHtmlString, Error, Serialization, SerializationEnv};
+
+
+/// Html that has been rendered once and is written verbatim.
+///
+/// Useful for the parts of a page that never change (navigation bars, footers, icons...),
+/// which are then written without going through the tag machinery again.
+/// Cloning a `Prerendered` is cheap, as the rendered string is shared.
+///
+/// It is rendered without an environment, so it doesn't follow the serialization of the
+/// document it is written into: use [`Prerendered::with_serialization`] for XHTML or XML.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let footer = Prerendered::new(html!(footer { p { "Bye!" } })).unwrap();
+///
+/// let page = html!(body { (&footer) }).to_html_string().unwrap();
+/// assert_eq!(page, "<body><footer><p>Bye!</p></footer></body>");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Prerendered {
+    html: Arc<str>,
+}
+
+impl Prerendered {
+    /// Renders `html` into a new `Prerendered`, as HTML.
+    pub fn new(html: impl Html) -> Result<Self, Error> {
+        Ok(Self { html: html.to_html_string()?.into() })
+    }
+
+    /// Renders `html` into a new `Prerendered`, with the given serialization.
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    ///
+    /// let br = Prerendered::with_serialization(tags::br(Empty, Empty), Serialization::Xhtml).unwrap();
+    /// assert_eq!(br.as_str(), "<br/>");
+    /// ```
+    pub fn with_serialization(html: impl Html, serialization: Serialization) -> Result<Self, Error> {
+        let mut env = SerializationEnv::new(String::new(), serialization);
+        env.write_html(html)?;
+        Ok(Self { html: env.into_inner().into() })
+    }
+
+    /// Creates a `Prerendered` from HTML rendered by the crate.
+    pub(crate) fn from_rendered(html: String) -> Self {
+        Self { html: html.into() }
+    }
+
+    /// Returns the rendered HTML.
+    pub fn as_str(&self) -> &str {
+        &self.html
+    }
+}
+
+impl Html for Prerendered {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (&self).size_hint()
+    }
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        (&self).write_html(env)
+    }
+}
+
+impl Html for &Prerendered {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.html.len(), Some(self.html.len()))
+    }
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        env.write_str(&self.html)?;
+        Ok(())
+    }
+}
+
+/// Html that is prerendered lazily, on first use.
+///
+/// It is meant to be used in a `static`, see [`static_html!`](crate::static_html).
+pub struct LazyHtml {
+    html: OnceLock<Prerendered>,
+    init: fn() -> Prerendered,
+}
+
+impl LazyHtml {
+    /// Creates a new `LazyHtml` that will be rendered with `init`.
+    pub const fn new(init: fn() -> Prerendered) -> Self {
+        Self { html: OnceLock::new(), init }
+    }
+
+    /// Returns the prerendered HTML, rendering it if it is the first use.
+    pub fn get(&self) -> &Prerendered {
+        self.html.get_or_init(self.init)
+    }
+}
+
+impl Html for &LazyHtml {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.get().size_hint()
+    }
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        self.get().write_html(env)
+    }
+}
+
+/// Declares a `static` [`LazyHtml`], rendered on first use.
+///
+/// # Panics
+/// Panics on first use if rendering the HTML fails.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// static_html! {
+///     /// The navigation bar.
+///     static NAV = html!(nav { a href="/" { "Home" } });
+/// }
+///
+/// let page = html!(body { (&NAV) }).to_html_string().unwrap();
+/// assert_eq!(page, "<body><nav><a href=\"/\">Home</a></nav></body>");
+/// ```
+#[macro_export]
+macro_rules! static_html {
+    ($($(#[$attr:meta])* $vis:vis static $name:ident = $html:expr;)*) => {
+        $(
+            $(#[$attr])*
+            $vis static $name: $crate::LazyHtml = $crate::LazyHtml::new(|| {
+                $crate::Prerendered::new($html).expect("failed to prerender static HTML")
+            });
+        )*
+    };
+}
+
+/// An environment that renders into a string, with the serialization and the context of
+/// another environment, and collects the assets declared while rendering.
+pub(crate) struct BufferEnv<'e, E: HtmlEnv> {
+    pub(crate) buffer: String,
+    pub(crate) assets: Assets,
+    parent: &'e E,
+}
+
+impl<'e, E: HtmlEnv> BufferEnv<'e, E> {
+    pub(crate) fn new(parent: &'e E) -> Self {
+        Self { buffer: String::new(), assets: Assets::new(), parent }
+    }
+}
+
+impl<'e, E: HtmlEnv> Write for BufferEnv<'e, E> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.buffer.write_str(s)
+    }
+}
+
+impl<'e, E: HtmlEnv> HtmlEnv for BufferEnv<'e, E> {
+    fn serialization(&self) -> Serialization {
+        self.parent.serialization()
+    }
+    fn assets(&mut self) -> Option<&mut Assets> {
+        Some(&mut self.assets)
+    }
+    fn context_value(&self, type_id: TypeId) -> Option<&dyn Any> {
+        self.parent.context_value(type_id)
+    }
+}
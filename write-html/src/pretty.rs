@@ -0,0 +1,288 @@
+use std::any::{Any, TypeId};
+use std::fmt::Write;
+
+use crate::{Assets, HtmlEnv, Error, Serialization};
+
+
+/// Elements laid out inline, which are kept on the current line.
+const INLINE_ELEMENTS: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "br", "button", "cite", "code", "data", "del", "dfn", "em",
+    "i", "img", "input", "ins", "kbd", "label", "mark", "meter", "output", "progress", "q", "rp",
+    "rt", "ruby", "s", "samp", "select", "small", "span", "strong", "sub", "sup", "time", "u",
+    "var", "wbr",
+];
+
+/// Elements whose content is whitespace-sensitive, which are written unchanged.
+const WHITESPACE_SENSITIVE_ELEMENTS: &[&str] = &["pre", "textarea"];
+
+/// An [`HtmlEnv`] that indents the written HTML, to make it readable.
+///
+/// Block elements are put on their own line and indented according to their depth, unless
+/// the whole element fits in the maximum line width.
+/// Inline elements (like `span`, `a` or `em`) are kept on the current line, and the content of
+/// whitespace-sensitive elements (like `pre` or `textarea`) is written unchanged.
+///
+/// Elements are buffered until they are closed, the output can be retrieved with [`PrettyEnv::into_inner`].
+///
+/// Note that only the whitespace around block elements is changed, so the rendering of the
+/// page may differ only where this whitespace is significant (for example, with `white-space: pre` in CSS).
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let mut env = PrettyEnv::new(String::new()).with_max_line_width(30);
+/// env.write_html(html!(
+///     ul {
+///         li { "Item " em { "one" } }
+///         li { "Item " em { "two" } ", with a longer text" }
+///     }
+///     pre { "  unchanged" }
+/// )).unwrap();
+/// assert_eq!(env.into_inner().unwrap(), "\
+/// <ul>
+///   <li>Item <em>one</em></li>
+///   <li>
+///     Item <em>two</em>, with a longer text
+///   </li>
+/// </ul>
+/// <pre>  unchanged</pre>");
+///
+/// // the width is counted in characters, not in bytes
+/// let mut env = PrettyEnv::new(String::new()).with_max_line_width(18);
+/// env.write_html(html!(p { "héllo wörld" })).unwrap();
+/// assert_eq!(env.into_inner().unwrap(), "<p>héllo wörld</p>");
+/// ```
+pub struct PrettyEnv<W: HtmlEnv> {
+    w: W,
+    indent: usize,
+    max_line_width: usize,
+    /// Whether each of the open tags has a frame.
+    open: Vec<bool>,
+    /// The buffered elements, from the outermost.
+    frames: Vec<Frame>,
+    /// Whether something has been written to `w`.
+    written: bool,
+    /// Whether the last thing written to `w` is a block element.
+    after_block: bool,
+}
+
+impl<W: HtmlEnv> PrettyEnv<W> {
+    /// Creates a new `PrettyEnv` that will write to `w`.
+    ///
+    /// By default, the indentation is 2 spaces and the maximum line width is 80.
+    pub fn new(w: W) -> Self {
+        Self {
+            w,
+            indent: 2,
+            max_line_width: 80,
+            open: Vec::new(),
+            frames: Vec::new(),
+            written: false,
+            after_block: false,
+        }
+    }
+
+    /// Sets the number of spaces of each indentation level.
+    pub fn with_indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Sets the maximum width of an element that is kept on a single line.
+    pub fn with_max_line_width(mut self, max_line_width: usize) -> Self {
+        self.max_line_width = max_line_width;
+        self
+    }
+
+    /// Returns the underlying environment.
+    ///
+    /// Elements that are not closed yet (after an error) are written unformatted.
+    pub fn into_inner(mut self) -> Result<W, Error> {
+        let mut unclosed = String::new();
+        for frame in std::mem::take(&mut self.frames) {
+            unclosed.push_str(&frame.opening);
+            for segment in &frame.segments {
+                unclosed.push_str(segment.as_str());
+            }
+            unclosed.push_str(&frame.closing);
+        }
+        self.write_str(&unclosed)?;
+        Ok(self.w)
+    }
+}
+
+impl<W: HtmlEnv> Write for PrettyEnv<W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.push_str(s);
+            return Ok(());
+        }
+
+        if s.is_empty() {
+            return Ok(());
+        }
+        if self.after_block {
+            self.w.write_str("\n")?;
+            self.after_block = false;
+        }
+        self.written = true;
+        self.w.write_str(s)
+    }
+}
+
+impl<W: HtmlEnv> HtmlEnv for PrettyEnv<W> {
+    fn serialization(&self) -> Serialization {
+        self.w.serialization()
+    }
+
+    fn before_open_tag(&mut self, tag: &str) -> Result<(), Error> {
+        let in_whitespace_sensitive = self.frames.last().is_some_and(|f| f.whitespace_sensitive);
+        let has_frame = !in_whitespace_sensitive && !INLINE_ELEMENTS.contains(&tag);
+        self.open.push(has_frame);
+        if has_frame {
+            self.frames.push(Frame::new(WHITESPACE_SENSITIVE_ELEMENTS.contains(&tag)));
+        }
+        Ok(())
+    }
+
+    fn after_open_tag(&mut self, _tag: &str) -> Result<(), Error> {
+        if self.open.last() == Some(&true) {
+            self.frames.last_mut().unwrap().state = State::Content;
+        }
+        Ok(())
+    }
+
+    fn before_close_tag(&mut self, _tag: &str) -> Result<(), Error> {
+        if self.open.last() == Some(&true) {
+            self.frames.last_mut().unwrap().state = State::Closing;
+        }
+        Ok(())
+    }
+
+    fn after_close_tag(&mut self, _tag: &str) -> Result<(), Error> {
+        if self.open.pop() != Some(true) {
+            return Ok(());
+        }
+
+        let frame = self.frames.pop().unwrap();
+        let depth = self.frames.len();
+        let s = frame.format(depth, self.indent, self.max_line_width);
+
+        if let Some(parent) = self.frames.last_mut() {
+            parent.segments.push(Segment::Block(s));
+            return Ok(());
+        }
+
+        if self.written {
+            self.w.write_str("\n")?;
+        }
+        self.w.write_str(&s)?;
+        self.written = true;
+        self.after_block = true;
+        Ok(())
+    }
+
+    fn assets(&mut self) -> Option<&mut Assets> {
+        self.w.assets()
+    }
+    fn implicit_close(&mut self, tag: &str) {
+        self.w.implicit_close(tag)
+    }
+    fn context_value(&self, type_id: TypeId) -> Option<&dyn Any> {
+        self.w.context_value(type_id)
+    }
+}
+
+/// Where the writes go inside a [`Frame`].
+enum State {
+    Opening,
+    Content,
+    Closing,
+}
+
+enum Segment {
+    Inline(String),
+    Block(String),
+}
+
+impl Segment {
+    fn as_str(&self) -> &str {
+        match self {
+            Segment::Inline(s) | Segment::Block(s) => s,
+        }
+    }
+}
+
+/// A buffered block element.
+struct Frame {
+    opening: String,
+    segments: Vec<Segment>,
+    closing: String,
+    state: State,
+    whitespace_sensitive: bool,
+}
+
+impl Frame {
+    fn new(whitespace_sensitive: bool) -> Self {
+        Self {
+            opening: String::new(),
+            segments: Vec::new(),
+            closing: String::new(),
+            state: State::Opening,
+            whitespace_sensitive,
+        }
+    }
+
+    fn push_str(&mut self, s: &str) {
+        match self.state {
+            State::Opening => self.opening.push_str(s),
+            State::Content => match self.segments.last_mut() {
+                Some(Segment::Inline(last)) => last.push_str(s),
+                _ => self.segments.push(Segment::Inline(s.to_owned())),
+            },
+            State::Closing => self.closing.push_str(s),
+        }
+    }
+
+    /// Formats the element, at the given depth.
+    fn format(self, depth: usize, indent: usize, max_line_width: usize) -> String {
+        let width = self.opening.chars().count()
+            + self.segments.iter().map(|s| s.as_str().chars().count()).sum::<usize>()
+            + self.closing.chars().count();
+        let multiline = self.segments.iter().any(|s| s.as_str().contains('\n'));
+
+        let mut s = self.opening;
+
+        if self.whitespace_sensitive || (!multiline && depth * indent + width <= max_line_width) {
+            for segment in &self.segments {
+                s.push_str(segment.as_str());
+            }
+            s.push_str(&self.closing);
+            return s;
+        }
+
+        for segment in &self.segments {
+            let segment = match segment {
+                Segment::Inline(text) => text.trim(),
+                Segment::Block(block) => block,
+            };
+            if segment.is_empty() {
+                continue;
+            }
+            s.push('\n');
+            push_indent(&mut s, (depth + 1) * indent);
+            s.push_str(segment);
+        }
+        if !self.closing.is_empty() {
+            s.push('\n');
+            push_indent(&mut s, depth * indent);
+            s.push_str(&self.closing);
+        }
+        s
+    }
+}
+
+fn push_indent(s: &mut String, n: usize) {
+    s.extend(std::iter::repeat_n(' ', n));
+}
@@ -0,0 +1,122 @@
+use std::any::{Any, TypeId};
+use std::fmt::Write;
+
+use crate::{Assets, HtmlEnv, Error};
+
+
+/// The syntax used to write a document, see [`HtmlEnv::serialization`].
+///
+/// | | void elements | empty elements | boolean attributes | [`Doctype`](crate::Doctype) |
+/// |---|---|---|---|---|
+/// | `Html` | `<br>` | `<p></p>` | `checked` | `<!DOCTYPE html>` |
+/// | `Xhtml` | `<br/>` | `<p></p>` | `checked="checked"` | `<!DOCTYPE html>` |
+/// | `Xml` | `<br/>` | `<p/>` | `checked="checked"` | `<?xml version="1.0" encoding="UTF-8"?>` |
+///
+/// In `Html` mode, void elements are written as defined by their [`Compactability`](crate::Compactability).
+/// In `Xhtml` mode, the [polyglot markup](https://www.w3.org/TR/html-polyglot/) is written, which
+/// is parsed in the same way as HTML and as XML, and the `html` element gets its `xmlns` attribute.
+/// In `Xml` mode, void elements are not checked, so any element can have content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Serialization {
+    /// The HTML syntax.
+    #[default]
+    Html,
+    /// The XHTML syntax, compatible with the HTML one.
+    Xhtml,
+    /// The XML syntax, for documents that are not HTML.
+    Xml,
+}
+
+/// The namespace of XHTML.
+pub(crate) const XHTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
+
+/// An [`HtmlEnv`] that writes with the given [`Serialization`].
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let page = || html!(
+///     (Doctype)
+///     html {
+///         body {
+///             input type="checkbox" checked;
+///             p;
+///         }
+///     }
+/// );
+///
+/// assert_eq!(
+///     page().to_html_string().unwrap(),
+///     "<!DOCTYPE html><html><body><input type=\"checkbox\" checked><p></p></body></html>"
+/// );
+///
+/// let mut env = SerializationEnv::new(String::new(), Serialization::Xhtml);
+/// env.write_html(page()).unwrap();
+/// assert_eq!(
+///     env.into_inner(),
+///     concat!(
+///         "<!DOCTYPE html><html xmlns=\"http://www.w3.org/1999/xhtml\"><body>",
+///         "<input type=\"checkbox\" checked=\"checked\"/><p></p></body></html>",
+///     )
+/// );
+///
+/// let mut env = SerializationEnv::new(String::new(), Serialization::Xml);
+/// env.write_html(page()).unwrap();
+/// assert_eq!(
+///     env.into_inner(),
+///     concat!(
+///         "<?xml version=\"1.0\" encoding=\"UTF-8\"?><html><body>",
+///         "<input type=\"checkbox\" checked=\"checked\"/><p/></body></html>",
+///     )
+/// );
+/// ```
+pub struct SerializationEnv<W: HtmlEnv> {
+    w: W,
+    serialization: Serialization,
+}
+
+impl<W: HtmlEnv> SerializationEnv<W> {
+    /// Creates a new `SerializationEnv` that will write to `w` with the given serialization.
+    pub fn new(w: W, serialization: Serialization) -> Self {
+        Self { w, serialization }
+    }
+
+    /// Returns the underlying environment.
+    pub fn into_inner(self) -> W {
+        self.w
+    }
+}
+
+impl<W: HtmlEnv> Write for SerializationEnv<W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.w.write_str(s)
+    }
+}
+
+impl<W: HtmlEnv> HtmlEnv for SerializationEnv<W> {
+    fn serialization(&self) -> Serialization {
+        self.serialization
+    }
+    fn before_open_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.w.before_open_tag(tag)
+    }
+    fn after_open_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.w.after_open_tag(tag)
+    }
+    fn before_close_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.w.before_close_tag(tag)
+    }
+    fn after_close_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.w.after_close_tag(tag)
+    }
+    fn assets(&mut self) -> Option<&mut Assets> {
+        self.w.assets()
+    }
+    fn implicit_close(&mut self, tag: &str) {
+        self.w.implicit_close(tag)
+    }
+    fn context_value(&self, type_id: TypeId) -> Option<&dyn Any> {
+        self.w.context_value(type_id)
+    }
+}
@@ -0,0 +1,497 @@
+/*!
+Provides [`Url`] and [`SitemapWriter`], to write [sitemaps](https://www.sitemaps.org/protocol.html).
+
+A sitemap is a `urlset` document (see [`urlset`]) with at most 50,000 URLs and 50 MiB.
+[`SitemapWriter`] splits larger lists of URLs into several sitemaps and a sitemap index
+(see [`index`]) that lists them.
+The [image](https://developers.google.com/search/docs/crawling-indexing/sitemaps/image-sitemaps)
+and [alternate language](https://developers.google.com/search/docs/specialty/international/localized-versions#sitemap)
+extensions are supported.
+
+# Example
+```
+use write_html::*;
+use write_html::sitemap::{self, Url, ChangeFreq};
+
+let urls = vec![
+    Url::new("https://example.com/")
+        .with_lastmod(DateTime::from_ymd_hms(2024, 3, 1, 0, 0, 0).unwrap())
+        .with_changefreq(ChangeFreq::Daily)
+        .with_priority(1.0),
+    Url::new("https://example.com/en/about")
+        .with_alternate("de", "https://example.com/de/about")
+        .with_image("https://example.com/team.jpg"),
+];
+
+assert_eq!(sitemap::urlset(urls).to_html_string().unwrap(), concat!(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+    "<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\"",
+    " xmlns:image=\"http://www.google.com/schemas/sitemap-image/1.1\"",
+    " xmlns:xhtml=\"http://www.w3.org/1999/xhtml\">",
+    "<url><loc>https://example.com/</loc><lastmod>2024-03-01T00:00:00Z</lastmod>",
+    "<changefreq>daily</changefreq><priority>1</priority></url>",
+    "<url><loc>https://example.com/en/about</loc>",
+    "<xhtml:link rel=\"alternate\" hreflang=\"de\" href=\"https://example.com/de/about\"/>",
+    "<image:image><image:loc>https://example.com/team.jpg</image:loc></image:image></url>",
+    "</urlset>",
+));
+```
+*/
+
+use std::fmt::Write;
+
+use crate::{
+    xml::{self, text_element}, Compactability, DateTime, Error, Html, HtmlEnv, Serialization,
+    SerializationEnv, XHTML_NAMESPACE,
+};
+
+
+/// The namespace of sitemaps.
+pub const SITEMAP_NAMESPACE: &str = "http://www.sitemaps.org/schemas/sitemap/0.9";
+
+/// The namespace of the image extension.
+pub const IMAGE_NAMESPACE: &str = "http://www.google.com/schemas/sitemap-image/1.1";
+
+/// The maximum number of URLs in a sitemap, and of sitemaps in a sitemap index.
+pub const MAX_URLS: usize = 50_000;
+
+/// The maximum size of a sitemap, in bytes (50 MiB, before compression).
+pub const MAX_SIZE: usize = 50 * 1024 * 1024;
+
+/// How frequently a page is likely to change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeFreq {
+    /// The page changes each time it is accessed.
+    Always,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+    /// The page is archived.
+    Never,
+}
+
+impl ChangeFreq {
+    /// Returns the value of `<changefreq>`, like `daily`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeFreq::Always => "always",
+            ChangeFreq::Hourly => "hourly",
+            ChangeFreq::Daily => "daily",
+            ChangeFreq::Weekly => "weekly",
+            ChangeFreq::Monthly => "monthly",
+            ChangeFreq::Yearly => "yearly",
+            ChangeFreq::Never => "never",
+        }
+    }
+}
+
+/// A URL of a sitemap, written as a `<url>` element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Url {
+    loc: String,
+    lastmod: Option<DateTime>,
+    changefreq: Option<ChangeFreq>,
+    priority: Option<f32>,
+    alternates: Vec<(String, String)>,
+    images: Vec<String>,
+}
+
+impl Url {
+    /// Creates a new `Url` with the given location.
+    pub fn new(loc: impl Into<String>) -> Self {
+        Self {
+            loc: loc.into(),
+            lastmod: None,
+            changefreq: None,
+            priority: None,
+            alternates: Vec::new(),
+            images: Vec::new(),
+        }
+    }
+
+    /// Sets the date of the last modification.
+    pub fn with_lastmod(mut self, lastmod: DateTime) -> Self {
+        self.lastmod = Some(lastmod);
+        self
+    }
+
+    /// Sets how frequently the page is likely to change.
+    pub fn with_changefreq(mut self, changefreq: ChangeFreq) -> Self {
+        self.changefreq = Some(changefreq);
+        self
+    }
+
+    /// Sets the priority relative to the other URLs of the site, between `0.0` and `1.0`.
+    ///
+    /// It is written with all its decimals, without a trailing `.0`.
+    /// Writing the URL fails with [`Error::Validation`] if the priority is out of this range.
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    /// use write_html::sitemap::Url;
+    ///
+    /// let url = Url::new("https://example.com/").with_priority(0.25);
+    /// assert!(xml::to_string(url).unwrap().contains("<priority>0.25</priority>"));
+    /// ```
+    pub fn with_priority(mut self, priority: f32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Adds a version of the page in another language, written as
+    /// `<xhtml:link rel="alternate" hreflang="..." href="..."/>`.
+    pub fn with_alternate(mut self, hreflang: impl Into<String>, href: impl Into<String>) -> Self {
+        self.alternates.push((hreflang.into(), href.into()));
+        self
+    }
+
+    /// Adds an image of the page, written as `<image:image>`.
+    pub fn with_image(mut self, loc: impl Into<String>) -> Self {
+        self.images.push(loc.into());
+        self
+    }
+}
+
+impl Html for Url {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        (&self).write_html(env)
+    }
+}
+
+impl Html for &Url {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        if self.priority.is_some_and(|p| !(0.0..=1.0).contains(&p)) {
+            return Err(Error::Validation(format!("the priority of {} is not between 0 and 1", self.loc)));
+        }
+
+        let mut env = SerializationEnv::new(env, Serialization::Xml);
+        let mut url = env.open_tag("url", Compactability::No)?.inner_html()?;
+        text_element(&mut url, "loc", &self.loc)?;
+        if let Some(lastmod) = self.lastmod {
+            text_element(&mut url, "lastmod", lastmod.rfc3339())?;
+        }
+        if let Some(changefreq) = self.changefreq {
+            text_element(&mut url, "changefreq", changefreq.as_str())?;
+        }
+        if let Some(priority) = self.priority {
+            text_element(&mut url, "priority", priority)?;
+        }
+        for (hreflang, href) in &self.alternates {
+            let attributes = [("rel", "alternate"), ("hreflang", hreflang.as_str()), ("href", href.as_str())];
+            url.child(xml::element("xhtml:link", attributes, crate::Empty))?;
+        }
+        for image in &self.images {
+            let mut image_element = url.open_tag("image:image", Compactability::No)?.inner_html()?;
+            text_element(&mut image_element, "image:loc", image)?;
+            image_element.close()?;
+        }
+        url.close()?;
+        Ok(())
+    }
+}
+
+/// A sitemap, see [`urlset`].
+pub struct UrlSet<I>(I);
+
+/// Creates a sitemap with the given URLs, written as a `urlset` document.
+///
+/// The limits of [`MAX_URLS`] and [`MAX_SIZE`] are not checked, see [`SitemapWriter`] for that.
+///
+/// See the [module documentation](self) for an example.
+pub fn urlset<I: IntoIterator<Item = Url>>(urls: I) -> UrlSet<I> {
+    UrlSet(urls)
+}
+
+impl<I: IntoIterator<Item = Url>> Html for UrlSet<I> {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        write_urlset(env, self.0)
+    }
+}
+
+/// Writes a `urlset` document with the given `<url>` elements.
+fn write_urlset(env: &mut impl HtmlEnv, urls: impl IntoIterator<Item = impl Html>) -> Result<(), Error> {
+    let mut env = SerializationEnv::new(env, Serialization::Xml);
+    env.write_html(xml::Declaration)?;
+    let mut urlset = env.open_tag("urlset", Compactability::No)?
+        .with_attributes(xml::default_namespace(SITEMAP_NAMESPACE))?
+        .with_attributes(xml::namespace("image", IMAGE_NAMESPACE))?
+        .with_attributes(xml::namespace("xhtml", XHTML_NAMESPACE))?
+        .inner_html()?;
+    for url in urls {
+        urlset.child(url)?;
+    }
+    urlset.close()?;
+    Ok(())
+}
+
+/// A sitemap listed in a sitemap index, see [`index`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexEntry {
+    /// The location of the sitemap.
+    pub loc: String,
+    /// The date of the last modification of the sitemap.
+    pub lastmod: Option<DateTime>,
+}
+
+/// A sitemap index, see [`index`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SitemapIndex<I>(I);
+
+/// Creates a sitemap index, which lists the given sitemaps.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+/// use write_html::sitemap::{self, IndexEntry};
+///
+/// let index = sitemap::index([
+///     IndexEntry { loc: "https://example.com/sitemap-1.xml".to_owned(), lastmod: None },
+/// ]);
+/// assert_eq!(index.to_html_string().unwrap(), concat!(
+///     "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+///     "<sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">",
+///     "<sitemap><loc>https://example.com/sitemap-1.xml</loc></sitemap>",
+///     "</sitemapindex>",
+/// ));
+/// ```
+pub fn index<I: IntoIterator<Item = IndexEntry>>(sitemaps: I) -> SitemapIndex<I> {
+    SitemapIndex(sitemaps)
+}
+
+impl<I: IntoIterator<Item = IndexEntry>> Html for SitemapIndex<I> {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        let mut env = SerializationEnv::new(env, Serialization::Xml);
+        env.write_html(xml::Declaration)?;
+        let mut index = env.open_tag("sitemapindex", Compactability::No)?
+            .with_attributes(xml::default_namespace(SITEMAP_NAMESPACE))?
+            .inner_html()?;
+        for entry in self.0 {
+            let mut sitemap = index.open_tag("sitemap", Compactability::No)?.inner_html()?;
+            text_element(&mut sitemap, "loc", &entry.loc)?;
+            if let Some(lastmod) = entry.lastmod {
+                text_element(&mut sitemap, "lastmod", lastmod.rfc3339())?;
+            }
+            sitemap.close()?;
+        }
+        index.close()?;
+        Ok(())
+    }
+}
+
+/// The sitemaps written by [`SitemapWriter::write`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sitemaps<E> {
+    /// The environments the sitemaps were written into, in order.
+    pub sitemaps: Vec<E>,
+    /// The sitemap index that lists them, if there are several sitemaps.
+    pub index: Option<SitemapIndex<Vec<IndexEntry>>>,
+}
+
+/// Writes sitemaps, split so that each of them is within the limits of the protocol.
+///
+/// The URLs are streamed into the environments of the sitemaps, like files, as they come.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+/// use write_html::sitemap::{SitemapWriter, Url};
+///
+/// let urls = (1..=5).map(|i| Url::new(format!("https://example.com/{}", i)));
+/// let sitemaps = SitemapWriter::new()
+///     .with_max_urls(2)
+///     .write(urls, |i| format!("https://example.com/sitemap-{}.xml", i + 1), |_| Ok(String::new()))
+///     .unwrap();
+///
+/// assert_eq!(sitemaps.sitemaps.len(), 3);
+/// assert!(sitemaps.sitemaps[2].contains("<url><loc>https://example.com/5</loc></url>"));
+/// let index = sitemaps.index.unwrap().to_html_string().unwrap();
+/// assert!(index.contains("<loc>https://example.com/sitemap-3.xml</loc>"));
+///
+/// let urls = (1..=5).map(|i| Url::new(format!("https://example.com/{}", i)));
+/// let sitemaps = SitemapWriter::new()
+///     .with_max_size(400)
+///     .write(urls, |i| format!("https://example.com/sitemap-{}.xml", i + 1), |_| Ok(String::new()))
+///     .unwrap();
+/// assert!(sitemaps.sitemaps.len() > 1);
+/// assert!(sitemaps.sitemaps.iter().all(|sitemap| sitemap.len() <= 400));
+/// ```
+///
+/// To write files, the environments can be [`IoHtmlEnv`](crate::IoHtmlEnv)s, which are then
+/// flushed with [`IoHtmlEnv::into_inner`](crate::IoHtmlEnv::into_inner):
+/// ```no_run
+/// use std::fs::File;
+/// use write_html::*;
+/// use write_html::sitemap::{SitemapWriter, Url};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let urls = (1..=100_000).map(|i| Url::new(format!("https://example.com/{}", i)));
+/// let sitemaps = SitemapWriter::new().write(
+///     urls,
+///     |i| format!("https://example.com/sitemap-{}.xml", i + 1),
+///     |i| Ok(IoHtmlEnv::new(File::create(format!("sitemap-{}.xml", i + 1))?)),
+/// )?;
+/// for sitemap in sitemaps.sitemaps {
+///     sitemap.into_inner()?;
+/// }
+/// if let Some(index) = sitemaps.index {
+///     IoHtmlEnv::new(File::create("sitemap.xml")?).render(index)?.flush()?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SitemapWriter {
+    max_urls: usize,
+    max_size: usize,
+}
+
+impl Default for SitemapWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SitemapWriter {
+    /// Creates a new `SitemapWriter`, with the limits of the protocol ([`MAX_URLS`] and [`MAX_SIZE`]).
+    pub fn new() -> Self {
+        Self { max_urls: MAX_URLS, max_size: MAX_SIZE }
+    }
+
+    /// Sets the maximum number of URLs in a sitemap, which can't be more than [`MAX_URLS`].
+    pub fn with_max_urls(mut self, max_urls: usize) -> Self {
+        self.max_urls = max_urls.clamp(1, MAX_URLS);
+        self
+    }
+
+    /// Sets the maximum size of a sitemap, in bytes, which can't be more than [`MAX_SIZE`].
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size.min(MAX_SIZE);
+        self
+    }
+
+    /// Writes the sitemaps of `urls`.
+    ///
+    /// The `i`-th sitemap (from 0) is written into the environment returned by `sitemap(i)`,
+    /// which is created when the previous sitemap is full.
+    /// If the URLs don't fit in a single sitemap, a sitemap index is returned, where the
+    /// location of the `i`-th sitemap is `sitemap_loc(i)`.
+    ///
+    /// # Errors
+    /// Returns [`Error::Validation`] if a URL doesn't fit in a sitemap by itself, or if there are
+    /// more sitemaps than an index can list, and the errors of `sitemap` and of the environments.
+    pub fn write<E: HtmlEnv>(
+        &self,
+        urls: impl IntoIterator<Item = Url>,
+        mut sitemap_loc: impl FnMut(usize) -> String,
+        mut sitemap: impl FnMut(usize) -> Result<E, Error>,
+    ) -> Result<Sitemaps<E>, Error> {
+        let overhead = written_size(urlset(std::iter::empty()))?;
+        let mut urls = urls.into_iter();
+        let mut next = self.next_url(&mut urls, overhead)?;
+
+        let mut sitemaps = Vec::new();
+        let mut entries = Vec::new();
+        loop {
+            let i = sitemaps.len();
+            if i == MAX_URLS {
+                return Err(Error::Validation(format!("more than {} sitemaps can't be listed in an index", MAX_URLS)));
+            }
+
+            let mut env = sitemap(i)?;
+            let mut chunk = Chunk { writer: self, urls: &mut urls, next: &mut next, overhead, count: 0, size: overhead, lastmod: None, error: None };
+            write_urlset(&mut env, &mut chunk)?;
+            let lastmod = chunk.lastmod;
+            if let Some(error) = chunk.error {
+                return Err(error);
+            }
+
+            sitemaps.push(env);
+            entries.push(IndexEntry { loc: sitemap_loc(i), lastmod });
+            if next.is_none() {
+                break;
+            }
+        }
+
+        let index = match sitemaps.len() {
+            1 => None,
+            _ => Some(index(entries)),
+        };
+        Ok(Sitemaps { sitemaps, index })
+    }
+
+    /// Returns the next URL of `urls`, with the size of its `<url>` element.
+    fn next_url(&self, urls: &mut impl Iterator<Item = Url>, overhead: usize) -> Result<Option<(Url, usize)>, Error> {
+        let Some(url) = urls.next() else {
+            return Ok(None);
+        };
+        let size = written_size(&url)?;
+        if overhead + size > self.max_size {
+            return Err(Error::Validation(format!("the sitemap entry of {} is too large", url.loc)));
+        }
+        Ok(Some((url, size)))
+    }
+}
+
+/// The URLs of a sitemap being written, taken from the URLs given to [`SitemapWriter::write`]
+/// until the sitemap is full.
+struct Chunk<'a, I> {
+    writer: &'a SitemapWriter,
+    urls: &'a mut I,
+    /// The next URL, with its size, which is the first one of the next sitemap if this one is full.
+    next: &'a mut Option<(Url, usize)>,
+    /// The size of the `urlset` document without its URLs.
+    overhead: usize,
+    count: usize,
+    /// The size of the sitemap so far.
+    size: usize,
+    /// The latest modification of the URLs.
+    lastmod: Option<DateTime>,
+    /// The error of the URL after the last one, which ends the sitemap.
+    error: Option<Error>,
+}
+
+impl<'a, I: Iterator<Item = Url>> Iterator for Chunk<'a, I> {
+    type Item = Url;
+
+    fn next(&mut self) -> Option<Url> {
+        let (_, size) = self.next.as_ref()?;
+        if self.count >= self.writer.max_urls || self.size + size > self.writer.max_size {
+            return None;
+        }
+
+        let (url, size) = self.next.take()?;
+        self.count += 1;
+        self.size += size;
+        self.lastmod = match (self.lastmod, url.lastmod) {
+            (Some(a), Some(b)) if a.timestamp() >= b.timestamp() => Some(a),
+            (a, b) => b.or(a),
+        };
+        match self.writer.next_url(self.urls, self.overhead) {
+            Ok(next) => *self.next = next,
+            Err(error) => self.error = Some(error),
+        }
+        Some(url)
+    }
+}
+
+/// Returns the size of `html` written as XML, without writing it anywhere.
+fn written_size(html: impl Html) -> Result<usize, Error> {
+    let mut env = SerializationEnv::new(Counter(0), Serialization::Xml);
+    env.write_html(html)?;
+    Ok(env.into_inner().0)
+}
+
+/// An environment that only counts the written bytes.
+struct Counter(usize);
+
+impl Write for Counter {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+impl HtmlEnv for Counter {}
@@ -1,25 +1,230 @@
-use std::fmt::Write;
+use std::borrow::Cow;
+use std::any::{Any, TypeId};
+use std::fmt::{Display, Write};
 
-use crate::{AttributeName, AttributeValue, escapes::StringEscaper, Attributes};
+use crate::{Assets, AttributeName, AttributeValue, escapes::{AttributeEscaper, XmlEscaper}, Attributes, Html, HtmlEnv, Error, Serialization, XHTML_NAMESPACE};
 
-struct TagOpeningData<'a, 't, W: Write> {
-    tag: &'t str,
+/// Tells whether the given string is a valid tag name.
+///
+/// A valid tag name is either made of ASCII letters and digits and starts with a letter (like
+/// the HTML, SVG and MathML elements), or is a
+/// [valid custom element name](https://html.spec.whatwg.org/multipage/custom-elements.html#valid-custom-element-name),
+/// which starts with a lowercase ASCII letter and contains a hyphen.
+///
+/// # Example
+/// ```
+/// use write_html::is_valid_tag_name;
+///
+/// assert!(is_valid_tag_name("h1"));
+/// assert!(is_valid_tag_name("foreignObject"));
+/// assert!(is_valid_tag_name("my-element"));
+/// assert!(!is_valid_tag_name("1h"));
+/// assert!(!is_valid_tag_name("div onclick"));
+/// assert!(!is_valid_tag_name("My-element"));
+/// ```
+pub fn is_valid_tag_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    let first_char = match chars.next() {
+        Some(c) => c,
+        None => return false,
+    };
+
+    if !first_char.is_ascii_alphabetic() {
+        return false;
+    }
+
+    if name.contains('-') {
+        return first_char.is_ascii_lowercase() && chars.all(is_custom_element_name_char);
+    }
+
+    chars.all(|c| c.is_ascii_alphanumeric())
+}
+
+/// The [void elements](https://html.spec.whatwg.org/multipage/syntax.html#void-elements) of HTML,
+/// which can't have content.
+pub(crate) const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source", "track", "wbr",
+];
+
+/// Tells whether the given tag is a void element, which can't have content (like `br` or `img`).
+///
+/// # Example
+/// ```
+/// use write_html::is_void_element;
+///
+/// assert!(is_void_element("img"));
+/// assert!(!is_void_element("div"));
+/// ```
+pub fn is_void_element(tag: &str) -> bool {
+    VOID_ELEMENTS.iter().any(|v| v.eq_ignore_ascii_case(tag))
+}
+
+/// Tells whether `c` is a `PCENChar` of the custom element name grammar.
+fn is_custom_element_name_char(c: char) -> bool {
+    matches!(c,
+        '-' | '.' | '0'..='9' | '_' | 'a'..='z' | '\u{B7}' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}'
+        | '\u{F8}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}' | '\u{200C}'..='\u{200D}' | '\u{203F}'..='\u{2040}'
+        | '\u{2070}'..='\u{218F}' | '\u{2C00}'..='\u{2FEF}' | '\u{3001}'..='\u{D7FF}' | '\u{F900}'..='\u{FDCF}'
+        | '\u{FDF0}'..='\u{FFFD}' | '\u{10000}'..='\u{EFFFF}'
+    )
+}
+
+/// The name of a tag, borrowed or owned.
+///
+/// It is created from a `&str`, a [`String`] or a [`Cow<str>`], so that tag names can be
+/// computed at runtime.
+/// The name is validated with [`is_valid_tag_name`] when the tag is opened, or by [`TagName::new`].
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let level = 2;
+/// let heading = tags::tag(format!("h{}", level), Empty, "Title".as_html_text(), Compactability::No);
+/// assert_eq!(heading.to_html_string().unwrap(), "<h2>Title</h2>");
+///
+/// let invalid = tags::tag("my element", Empty, Empty, Compactability::No);
+/// assert!(matches!(invalid.to_html_string().unwrap_err().innermost(), Error::InvalidTagName(_)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TagName<'t>(Cow<'t, str>);
+
+impl<'t> TagName<'t> {
+    /// Creates a new `TagName`, checking that it is valid.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidTagName`] if `name` is not a valid tag name.
+    pub fn new(name: impl Into<Cow<'t, str>>) -> Result<Self, Error> {
+        let name = TagName(name.into());
+        if !name.is_valid() {
+            return Err(Error::InvalidTagName(name.0.into_owned()));
+        }
+        Ok(name)
+    }
+
+    /// Tells whether the name is valid, see [`is_valid_tag_name`].
+    pub fn is_valid(&self) -> bool {
+        is_valid_tag_name(&self.0)
+    }
+
+    /// Returns the name.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns a `TagName` borrowing `self`.
+    pub fn borrowed(&self) -> TagName<'_> {
+        TagName(Cow::Borrowed(&self.0))
+    }
+
+    /// Returns an owned `TagName`.
+    pub fn into_owned(self) -> TagName<'static> {
+        TagName(Cow::Owned(self.0.into_owned()))
+    }
+}
+
+impl<'t> From<&'t str> for TagName<'t> {
+    fn from(name: &'t str) -> Self {
+        TagName(Cow::Borrowed(name))
+    }
+}
+
+impl<'t> From<&'t String> for TagName<'t> {
+    fn from(name: &'t String) -> Self {
+        TagName(Cow::Borrowed(name))
+    }
+}
+
+impl From<String> for TagName<'static> {
+    fn from(name: String) -> Self {
+        TagName(Cow::Owned(name))
+    }
+}
+
+impl<'t> From<Cow<'t, str>> for TagName<'t> {
+    fn from(name: Cow<'t, str>) -> Self {
+        TagName(name)
+    }
+}
+
+impl<'t> AsRef<str> for TagName<'t> {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<'t> Display for TagName<'t> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+struct TagOpeningData<'a, 't, W: HtmlEnv> {
+    tag: TagName<'t>,
     w: &'a mut W,
     compactability: Compactability,
+    serialization: Serialization,
+    /// Whether the `xmlns` attribute has been written.
+    xmlns: bool,
+}
+
+impl<'a, 't, W: HtmlEnv> TagOpeningData<'a, 't, W> {
+    /// Writes the attributes required by the serialization that have not been written yet.
+    fn write_required_attributes(&mut self) -> Result<(), Error> {
+        if self.serialization == Serialization::Xhtml && !self.xmlns && self.tag.0 == "html" {
+            write!(self.w, " xmlns=\"{}\"", XHTML_NAMESPACE)?;
+        }
+        Ok(())
+    }
 }
 
 
 /// Represents a tag that is being opened.
-pub struct TagOpening<'a, 't, W: Write> {
+pub struct TagOpening<'a, 't, W: HtmlEnv> {
     data: Option<TagOpeningData<'a, 't, W>>,
 }
 
-impl<'a, 't, W: Write> TagOpening<'a, 't, W> {
+impl<'a, 't, W: HtmlEnv> TagOpening<'a, 't, W> {
     /// Creates a new `TagOpening` that will write to `w`.
-    pub fn new(tag: &'t str, w: &'a mut W, compactability: Compactability) -> Result<Self, std::fmt::Error> {
+    ///
+    /// A void element (see [`is_void_element`]) is always compacted, even with
+    /// [`Compactability::No`], unless the [serialization](HtmlEnv::serialization) is
+    /// [`Serialization::Xml`].
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidTagName`] if `tag` is not a valid tag name, or not a valid
+    /// XML name (see [`xml::is_valid_name`](crate::xml::is_valid_name)) if the
+    /// [serialization](HtmlEnv::serialization) is [`Serialization::Xml`].
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    ///
+    /// let mut s = String::new();
+    /// s.open_tag("img", Compactability::No).unwrap().close().unwrap();
+    /// assert_eq!(s, "<img>");
+    /// ```
+    pub fn new(tag: impl Into<TagName<'t>>, w: &'a mut W, compactability: Compactability) -> Result<Self, Error> {
+        let tag = tag.into();
+        let serialization = w.serialization();
+        let valid = match serialization {
+            Serialization::Xml => crate::xml::is_valid_name(&tag.0),
+            Serialization::Html | Serialization::Xhtml => tag.is_valid(),
+        };
+        if !valid {
+            return Err(Error::InvalidTagName(tag.0.into_owned()));
+        }
+        // a void element has no closing tag, whatever the compactability
+        let compactability = match compactability {
+            Compactability::No if serialization != Serialization::Xml && is_void_element(&tag.0) => {
+                Compactability::Yes { final_slash: false }
+            }
+            compactability => compactability,
+        };
+        w.before_open_tag(&tag.0)?;
         w.write_str("<")?;
-        w.write_str(tag)?;
-        Ok(Self { data: Some(TagOpeningData { tag, w, compactability }) })
+        w.write_str(&tag.0)?;
+        Ok(Self { data: Some(TagOpeningData { tag, w, compactability, serialization, xmlns: false }) })
     }
 
     /// Adds an attribute to the tag.
@@ -27,22 +232,75 @@ impl<'a, 't, W: Write> TagOpening<'a, 't, W> {
     /// # Arguments
     /// * `name` - The name of the attribute.
     /// * `value` - The value of the attribute.
-    pub fn attr<'s>(
-        &'s mut self,
+    ///
+    /// If `value` is a unit value (like `()`), only the name is written, unless the
+    /// [serialization](HtmlEnv::serialization) is not HTML, in which case the name is repeated
+    /// as value (`checked="checked"`).
+    ///
+    /// In XML, the name is validated as an XML name (so it can have a prefix, like `xml:lang`).
+    /// The value is escaped with [`AttributeEscaper`], or with [`XmlEscaper`] in XHTML and XML.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidAttributeName`] if `name` is not a valid attribute name.
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    ///
+    /// let mut s = String::new();
+    /// let mut tag = s.open_tag("div", Compactability::No).unwrap();
+    /// tag.attr("class", "a").unwrap();
+    /// assert!(matches!(tag.attr("on click", "b"), Err(Error::InvalidAttributeName(_))));
+    ///
+    /// let mut env = SerializationEnv::new(String::new(), Serialization::Xhtml);
+    /// env.write_html(html!(a href="?q=\"a\"&page=2" { "Next" })).unwrap();
+    /// assert_eq!(env.into_inner(), "<a href=\"?q=&quot;a&quot;&amp;page=2\">Next</a>");
+    /// ```
+    pub fn attr(
+        &mut self,
         name: impl AttributeName,
         value: impl AttributeValue
-    ) -> Result<&'s mut Self, std::fmt::Error> {
+    ) -> Result<&mut Self, Error> {
         let data = self.data.as_mut().unwrap();
-        data.w.write_str(" ").unwrap();
 
-        assert!(name.is_valid_attribute_name());
-        name.write_attribute_name(data.w)?;
+        if data.serialization == Serialization::Html {
+            if !name.is_valid_attribute_name() {
+                let mut s = String::new();
+                name.write_attribute_name(&mut s)?;
+                return Err(Error::InvalidAttributeName(s));
+            }
+            data.w.write_str(" ")?;
+            name.write_attribute_name(data.w)?;
+        } else {
+            // the name is needed to expand boolean attributes and to find `xmlns`
+            let valid = name.is_valid_attribute_name();
+            let mut s = String::new();
+            name.write_attribute_name(&mut s)?;
+            let valid = match data.serialization {
+                Serialization::Xml => crate::xml::is_valid_name(&s),
+                Serialization::Html | Serialization::Xhtml => valid,
+            };
+            if !valid {
+                return Err(Error::InvalidAttributeName(s));
+            }
+            data.w.write_str(" ")?;
+            data.w.write_str(&s)?;
+            data.xmlns |= s == "xmlns";
+            if value.is_unit() {
+                write!(data.w, "=\"{}\"", s)?;
+                return Ok(self);
+            }
+        }
 
         if value.is_unit() {
             return Ok(self);
         } else {
             data.w.write_str("=\"")?;
-            value.write_attribute_value(&mut StringEscaper::new(data.w))?;
+            if data.serialization != Serialization::Html {
+                value.write_attribute_value(&mut XmlEscaper::new(data.w))?;
+            } else {
+                value.write_attribute_value(&mut AttributeEscaper::new(data.w))?;
+            }
             data.w.write_str("\"")?;
         }
 
@@ -52,60 +310,237 @@ impl<'a, 't, W: Write> TagOpening<'a, 't, W> {
     /// Adds an attribute to the tag.
     ///
     /// See [`attr`] for more information.
-    pub fn with_attr(mut self, name: impl AttributeName, value: impl AttributeValue) -> Result<Self, std::fmt::Error> {
+    pub fn with_attr(mut self, name: impl AttributeName, value: impl AttributeValue) -> Result<Self, Error> {
         self.attr(name, value)?;
 
         Ok(self)
     }
 
     /// Adds multiple attributes to the tag.
-    pub fn with_attributes(mut self, attributes: impl Attributes) -> Result<Self, std::fmt::Error> {
+    pub fn with_attributes(mut self, attributes: impl Attributes) -> Result<Self, Error> {
         attributes.write_attributes(&mut self)?;
         Ok(self)
     }
 
     /// Finishes the opening of the tag and returns a [`InsideTagHtml`] that can be used to write the contents of the tag.
-    pub fn inner_html(mut self) -> Result<InsideTagHtml<'a, 't, W>, std::fmt::Error> {
+    ///
+    /// # Errors
+    /// Returns [`Error::Validation`] if the tag is a void element (see [`is_void_element`]),
+    /// which can't have content, unless the [serialization](HtmlEnv::serialization) is
+    /// [`Serialization::Xml`]. The tag is then closed without content.
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    ///
+    /// let mut s = String::new();
+    /// let img = s.open_tag("img", Compactability::No).unwrap();
+    /// assert!(matches!(img.inner_html(), Err(Error::Validation(_))));
+    /// ```
+    pub fn inner_html(mut self) -> Result<InsideTagHtml<'a, 't, W>, Error> {
+        let data = self.data.as_ref().unwrap();
+        if data.serialization != Serialization::Xml && is_void_element(&data.tag.0) {
+            let tag = data.tag.0.clone().into_owned();
+            return Err(Error::Validation(format!("void element <{}> can't have content", tag)));
+        }
+
         // get the data out of self
-        let data = self.data.take().unwrap();
+        let mut data = self.data.take().unwrap();
+        data.write_required_attributes()?;
         data.w.write_str(">")?;
-        Ok(InsideTagHtml { tag: data.tag, w: data.w })
+        data.w.after_open_tag(&data.tag.0)?;
+        Ok(InsideTagHtml { tag: data.tag, w: Some(data.w) })
+    }
+
+    /// Closes the tag without contents, and returns the parent environment.
+    ///
+    /// Unlike dropping the `TagOpening`, this reports the errors that happen while closing the tag.
+    ///
+    /// Compactable tags are written as `<tag/>` if the [serialization](HtmlEnv::serialization)
+    /// is not HTML, whatever their `final_slash`, and all tags are if it is [`Serialization::Xml`].
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    ///
+    /// let mut s = String::new();
+    /// s.open_tag("br", Compactability::Yes { final_slash: false }).unwrap()
+    ///     .close().unwrap()
+    ///     .open_tag("div", Compactability::No).unwrap()
+    ///     .with_attr("id", "a").unwrap()
+    ///     .close().unwrap();
+    /// assert_eq!(s, "<br><div id=\"a\"></div>");
+    /// ```
+    pub fn close(mut self) -> Result<&'a mut W, Error> {
+        let mut data = self.data.take().unwrap();
+        data.write_required_attributes()?;
+        let compact = match (data.compactability, data.serialization) {
+            (_, Serialization::Xml) | (Compactability::Yes { .. }, Serialization::Xhtml) => Some(true),
+            (Compactability::Yes { final_slash }, Serialization::Html) => Some(final_slash),
+            (Compactability::No, _) => None,
+        };
+        if let Some(final_slash) = compact {
+            data.w.write_str(if final_slash { "/>" } else { ">" })?;
+            data.w.after_close_tag(&data.tag.0)?;
+            Ok(data.w)
+        } else {
+            data.w.write_str(">")?;
+            data.w.after_open_tag(&data.tag.0)?;
+            InsideTagHtml { tag: data.tag, w: Some(data.w) }.close()
+        }
     }
 }
 
-impl<'a, 't, W: Write> Drop for TagOpening<'a, 't, W> {
+impl<'a, 't, W: HtmlEnv> Drop for TagOpening<'a, 't, W> {
     fn drop(&mut self) {
         if let Some(data) = self.data.take() {
-            if let Compactability::Yes { final_slash } = data.compactability {
-                let _ = data.w.write_str(if final_slash { "/>" } else { ">" });
-            } else {
-                let _ = data.w.write_fmt(format_args!("></{}>", data.tag));
-            }
+            data.w.implicit_close(&data.tag.0);
+            let _ = TagOpening { data: Some(data) }.close();
         }
     }
 }
 
 /// Represents the environment inside a tag.
-pub struct InsideTagHtml<'a, 't, W: Write> {
-    tag: &'t str,
-    w: &'a mut W,
+///
+/// Besides the methods below, there is a method for each tag of [`tags`](crate::tags)
+/// (like [`div`](InsideTagHtml::div) or [`li`](InsideTagHtml::li)), which opens it inside this one.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let mut s = String::new();
+/// let mut ul = s.open_tag("ul", Compactability::No).unwrap().inner_html().unwrap();
+/// for i in 1..=2 {
+///     let mut li = ul.li().unwrap().with_attr("id", i).unwrap().inner_html().unwrap();
+///     li.text(format_args!("Item {}", i)).unwrap()
+///         .child(tags::em(Empty, "!".as_html_text())).unwrap();
+///     li.close().unwrap();
+/// }
+/// ul.close().unwrap();
+/// assert_eq!(s, "<ul><li id=\"1\">Item 1<em>!</em></li><li id=\"2\">Item 2<em>!</em></li></ul>");
+/// ```
+pub struct InsideTagHtml<'a, 't, W: HtmlEnv> {
+    tag: TagName<'t>,
+    /// The parent environment, `None` once the tag is closed.
+    w: Option<&'a mut W>,
 }
 
-impl<'a, 't, W: Write> InsideTagHtml<'a, 't, W> {
-    // TODO
+impl<'a, 't, W: HtmlEnv> InsideTagHtml<'a, 't, W> {
+    /// Opens a tag inside this one.
+    ///
+    /// See [`HtmlEnv::open_tag`] for more information.
+    pub fn open_tag<'s, 'u>(
+        &'s mut self,
+        tag: impl Into<TagName<'u>>,
+        compactability: Compactability
+    ) -> Result<TagOpening<'s, 'u, Self>, Error> {
+        TagOpening::new(tag, self, compactability)
+    }
+
+    /// Writes text, escaping it as necessary.
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    ///
+    /// let mut s = String::new();
+    /// s.open_tag("p", Compactability::No).unwrap().inner_html().unwrap()
+    ///     .text("1 < 2").unwrap()
+    ///     .text(3).unwrap();
+    /// assert_eq!(s, "<p>1 &lt; 23</p>");
+    /// ```
+    pub fn text(&mut self, text: impl Display) -> Result<&mut Self, Error> {
+        write!(self.write_html_text(), "{}", text)?;
+        Ok(self)
+    }
+
+    /// Writes raw HTML, without escaping it.
+    pub fn raw(&mut self, html: &str) -> Result<&mut Self, Error> {
+        self.write_str(html)?;
+        Ok(self)
+    }
+
+    /// Writes an [`Html`] inside the tag.
+    pub fn child(&mut self, html: impl Html) -> Result<&mut Self, Error> {
+        html.write_html(self)?;
+        Ok(self)
+    }
+
+    /// Closes the tag, and returns the parent environment.
+    ///
+    /// Unlike dropping the `InsideTagHtml`, this reports the errors that happen while closing the tag.
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    ///
+    /// let mut s = String::new();
+    /// let mut p = s.open_tag("p", Compactability::No).unwrap().inner_html().unwrap();
+    /// p.write_html("Hello".as_html_text()).unwrap();
+    /// p.close().unwrap()
+    ///     .write_html("!".as_html_text()).unwrap();
+    /// assert_eq!(s, "<p>Hello</p>!");
+    /// ```
+    pub fn close(mut self) -> Result<&'a mut W, Error> {
+        let w = self.w.take().unwrap();
+        close_tag(w, &self.tag.0)?;
+        Ok(w)
+    }
+
+    fn w(&mut self) -> &mut W {
+        self.w.as_mut().unwrap()
+    }
 }
 
-impl<'a, 't, W: Write> Drop for InsideTagHtml<'a, 't, W> {
+impl<'a, 't, W: HtmlEnv> Drop for InsideTagHtml<'a, 't, W> {
     fn drop(&mut self) {
-        let _ = self.w.write_str("</");
-        let _ = self.w.write_str(self.tag);
-        let _ = self.w.write_str(">");
+        if let Some(w) = self.w.take() {
+            w.implicit_close(&self.tag.0);
+            let _ = close_tag(w, &self.tag.0);
+        }
     }
 }
 
-impl<'a, 't, W: Write> Write for InsideTagHtml<'a, 't, W> {
+/// Writes the closing of a non-compacted tag.
+fn close_tag(w: &mut impl HtmlEnv, tag: &str) -> Result<(), Error> {
+    w.before_close_tag(tag)?;
+    w.write_str("</")?;
+    w.write_str(tag)?;
+    w.write_str(">")?;
+    w.after_close_tag(tag)
+}
+
+impl<'a, 't, W: HtmlEnv> Write for InsideTagHtml<'a, 't, W> {
     fn write_str(&mut self, s: &str) -> std::fmt::Result {
-        self.w.write_str(s)
+        self.w().write_str(s)
+    }
+}
+
+impl<'a, 't, W: HtmlEnv> HtmlEnv for InsideTagHtml<'a, 't, W> {
+    fn serialization(&self) -> Serialization {
+        self.w.as_ref().unwrap().serialization()
+    }
+    fn before_open_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.w().before_open_tag(tag)
+    }
+    fn after_open_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.w().after_open_tag(tag)
+    }
+    fn before_close_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.w().before_close_tag(tag)
+    }
+    fn after_close_tag(&mut self, tag: &str) -> Result<(), Error> {
+        self.w().after_close_tag(tag)
+    }
+    fn assets(&mut self) -> Option<&mut Assets> {
+        self.w().assets()
+    }
+    fn implicit_close(&mut self, tag: &str) {
+        self.w().implicit_close(tag)
+    }
+    fn context_value(&self, type_id: TypeId) -> Option<&dyn Any> {
+        self.w.as_ref().unwrap().context_value(type_id)
     }
 }
 
@@ -1,19 +1,36 @@
 /*!
 Provides functions for creating common tags.
+
+The content of a tag is any [`Html`]. Strings are written with
+[`as_html_text`](crate::AsHtml::as_html_text) (escaped) or [`as_html`](crate::AsHtml::as_html)
+(raw), and numbers or other [`Display`](std::fmt::Display) values with [`Text`](crate::Text):
+
+```
+use write_html::*;
+
+let count = 42;
+let s = tags::p(Empty, Text(count)).to_html_string().unwrap();
+assert_eq!(s, "<p>42</p>");
+```
 */
 
-use crate::{Attributes, Html, Compactability, Sum, AttributeName, AttributeValue, HtmlEnv, Empty};
+use crate::{Attributes, Html, Compactability, Sum, AttributeName, AttributeValue, HtmlEnv, Empty, add_size_hints, Error, InsideTagHtml, TagOpening, TagName, is_void_element, XHTML_NAMESPACE};
+
+pub mod svg;
+pub mod math;
 
 
 /// Represents a tag.
 ///
 /// TODO better docs
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Tag<'t, A: Attributes, I: Html, const SILENT: bool> { // TODO maybe with another type or an option
-    tag: &'t str,
+    tag: TagName<'t>,
     attributes: A,
     inner_html: I,
     compactability: Compactability,
+    /// Whether the tag itself is written, or only its content.
+    wrapped: bool,
 }
 
 impl<'n, A: Attributes, I: Html, const SILENT: bool> Tag<'n, A, I, SILENT> {
@@ -44,6 +61,7 @@ impl<'n, A: Attributes, I: Html, const SILENT: bool> Tag<'n, A, I, SILENT> {
             attributes: self.attributes,
             inner_html: Sum(self.inner_html, child),
             compactability: self.compactability,
+            wrapped: self.wrapped,
         }
     }
 
@@ -75,6 +93,7 @@ impl<'n, A: Attributes, I: Html, const SILENT: bool> Tag<'n, A, I, SILENT> {
             attributes: Sum(self.attributes, attributes),
             inner_html: self.inner_html,
             compactability: self.compactability,
+            wrapped: self.wrapped,
         }
     }
 
@@ -89,60 +108,141 @@ impl<'n, A: Attributes, I: Html, const SILENT: bool> Tag<'n, A, I, SILENT> {
             attributes: Sum(self.attributes, [(name, value)]),
             inner_html: self.inner_html,
             compactability: self.compactability,
+            wrapped: self.wrapped,
         }
     }
+
+    /// Writes the tag only if `cond` is `true`, otherwise only its content is written.
+    ///
+    /// The type is the same in both cases, so the condition can be decided at runtime.
+    ///
+    /// # Example
+    /// ```
+    /// use write_html::*;
+    ///
+    /// let link = |href: Option<&'static str>| tags::a(Empty, "Home".as_html_text())
+    ///     .attr("href", href.unwrap_or_default())
+    ///     .wrap_if(href.is_some());
+    ///
+    /// assert_eq!(link(Some("/")).to_html_string().unwrap(), "<a href=\"/\">Home</a>");
+    /// assert_eq!(link(None).to_html_string().unwrap(), "Home");
+    /// ```
+    ///
+    /// With the [`html!`](crate::html) macro, the condition is written as `if (cond)` after the attributes:
+    /// ```
+    /// use write_html::*;
+    ///
+    /// let highlighted = false;
+    /// let s = html!(p { strong .highlight if (highlighted) { "Text" } }).to_html_string().unwrap();
+    /// assert_eq!(s, "<p>Text</p>");
+    /// ```
+    pub fn wrap_if(mut self, cond: bool) -> Self {
+        self.wrapped &= cond;
+        self
+    }
+
+    /// Writes only the content of the tag if `cond` is `true`.
+    ///
+    /// This is the opposite of [`Tag::wrap_if`].
+    pub fn unwrap_when(mut self, cond: bool) -> Self {
+        self.wrapped &= !cond;
+        self
+    }
 }
 
 impl<'n, A: Attributes, I: Html, const SILENT: bool> Html for Tag<'n, A, I, SILENT> {
-    fn write_html(self, env: &mut impl crate::HtmlEnv) -> std::fmt::Result {
-        if SILENT {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if SILENT || !self.wrapped {
+            return self.inner_html.size_hint();
+        }
+        let hint = tags_size_hint(self.tag.as_str(), self.compactability, self.inner_html.is_unit());
+        let hint = add_size_hints(hint, self.attributes.size_hint());
+        add_size_hints(hint, self.inner_html.size_hint())
+    }
+    fn write_html(self, env: &mut impl crate::HtmlEnv) -> Result<(), Error> {
+        if SILENT || !self.wrapped {
             if !self.inner_html.is_unit() {
                 env.write_html(self.inner_html).map(|_| ())
             } else {
                 Ok(())
             }
         } else {
-            if self.inner_html.is_unit() {
-                env
-                    .open_tag(self.tag, self.compactability)?
-                    .with_attributes(self.attributes)
-                    .map(|_| ())
-            } else {
-                env
-                    .open_tag(self.tag, self.compactability)?
-                    .with_attributes(self.attributes)?
-                    .inner_html()?
-                    .write_html(self.inner_html)
-                    .map(|_| ())
-            }
+            let tag = self.tag;
+            let write = || {
+                let opening = env
+                    .open_tag(tag.borrowed(), self.compactability)?
+                    .with_attributes(self.attributes)?;
+                if self.inner_html.is_unit() {
+                    opening.close()?;
+                } else {
+                    let mut inside = opening.inner_html()?;
+                    inside.write_html(self.inner_html)?;
+                    inside.close()?;
+                }
+                Ok(())
+            };
+            write().map_err(|e: Error| e.in_tag(tag.as_str()))
         }
     }
 }
 
-// TODO comment tag
+/// Returns the size hint of the tags of an element, without its attributes and content.
+///
+/// It covers all the serializations: a compacted tag gets a final slash if it is not HTML,
+/// every tag without content is compacted in XML, and `<html>` gets an `xmlns` in XHTML.
+fn tags_size_hint(tag: &str, compactability: Compactability, is_unit: bool) -> (usize, Option<usize>) {
+    let len = tag.len();
+    let (lower, upper) = match compactability {
+        Compactability::Yes { final_slash } if is_unit => (len + 2 + final_slash as usize, len + 3), // <tag> or <tag/>
+        Compactability::No if is_unit && is_void_element(tag) => (len + 2, len + 3), // <tag> or <tag/>
+        Compactability::No if is_unit => (len + 3, 2 * len + 5), // <tag/> or <tag></tag>
+        _ => (2 * len + 5, 2 * len + 5), // <tag></tag>
+    };
+    let xmlns = if tag == "html" { " xmlns=\"\"".len() + XHTML_NAMESPACE.len() } else { 0 };
+    (lower, Some(upper + xmlns))
+}
 
-// TODO see https://www.w3schools.com/tags/
+// TODO comment tag
 
 /// Creates a new custom tag.
 ///
 /// See [`Tag`] for more information.
 ///
 /// # Arguments
-/// * `tag` - The name of the tag.
+/// * `tag` - The name of the tag, borrowed or owned (see [`TagName`]).
 /// * `attributes` - The attributes of the tag.
 /// * `inner_html` - The inner HTML of the tag.
 /// * `compactability` - Whether the tag can be compacted.
+///
+/// # Errors
+/// Writing the tag fails with [`Error::Validation`] if it is a void element with content
+/// (see [`is_void_element`](crate::is_void_element)).
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let name = "img";
+/// let err = tags::tag(name, Empty, "text".as_html_text(), Compactability::No)
+///     .to_html_string().unwrap_err();
+/// assert!(matches!(err.innermost(), Error::Validation(_)));
+///
+/// // a void element is compacted anyway
+/// let img = tags::tag(name, Empty, Empty, Compactability::No).to_html_string().unwrap();
+/// assert_eq!(img, "<img>");
+/// ```
 pub fn tag<'t, A: Attributes, I: Html>(
-    tag: &'t str,
+    tag: impl Into<TagName<'t>>,
     attributes: A,
     inner_html: I,
     compactability: Compactability,
 ) -> Tag<'t, A, I, false> {
     Tag {
-        tag,
+        tag: tag.into(),
         attributes,
         inner_html,
         compactability,
+        wrapped: true,
     }
 }
 
@@ -153,21 +253,130 @@ pub fn fragment<I: Html>(
     inner_html: I,
 ) -> Tag<'static, Empty, I, true> {
     Tag {
-        tag: "",
+        tag: "".into(),
         attributes: Empty,
         inner_html,
         compactability: Compactability::No,
+        wrapped: true,
+    }
+}
+
+/// Represents a void element, which can't have content (like `<br>` or `<img>`).
+///
+/// Unlike [`Tag`], it has no `child` method, so adding content to a void element doesn't compile:
+/// ```compile_fail
+/// use write_html::*;
+///
+/// tags::img(Empty, Empty).child("text".as_html_text());
+/// ```
+///
+/// Neither does a void element with children in the [`html!`](crate::html) macro:
+/// ```compile_fail
+/// use write_html::*;
+///
+/// html!(br { "text" });
+/// ```
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let img = tags::img(Empty, Empty).attr("src", "a.png");
+/// assert_eq!(img.to_html_string().unwrap(), "<img src=\"a.png\">");
+///
+/// // `<br>` in HTML, `<br/>` in XHTML
+/// assert_eq!(tags::br(Empty, Empty).size_hint(), (4, Some(5)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VoidTag<'t, A: Attributes> {
+    tag: TagName<'t>,
+    attributes: A,
+}
+
+impl<'n, A: Attributes> VoidTag<'n, A> {
+    /// Adds multiple attributes to the tag.
+    ///
+    /// See [`Tag::attributes`] for more information.
+    pub fn attributes<B: Attributes>(
+        self,
+        attributes: B,
+    ) -> VoidTag<'n, Sum<A, B>> {
+        VoidTag {
+            tag: self.tag,
+            attributes: Sum(self.attributes, attributes),
+        }
+    }
+
+    /// Adds an attribute to the tag.
+    pub fn attr<Name: AttributeName, Value: AttributeValue>(
+        self,
+        name: Name,
+        value: Value,
+    ) -> VoidTag<'n, Sum<A, [(Name, Value); 1]>> {
+        VoidTag {
+            tag: self.tag,
+            attributes: Sum(self.attributes, [(name, value)]),
+        }
     }
 }
 
+impl<'n, A: Attributes> Html for VoidTag<'n, A> {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let hint = tags_size_hint(self.tag.as_str(), Compactability::Yes { final_slash: false }, true);
+        add_size_hints(hint, self.attributes.size_hint())
+    }
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        let tag = self.tag;
+        let write = || {
+            env
+                .open_tag(tag.borrowed(), Compactability::Yes { final_slash: false })?
+                .with_attributes(self.attributes)?
+                .close()?;
+            Ok(())
+        };
+        write().map_err(|e: Error| e.in_tag(tag.as_str()))
+    }
+}
+
+/// Creates a new custom void element.
+///
+/// See [`VoidTag`] for more information.
+///
+/// # Arguments
+/// * `tag` - The name of the tag, borrowed or owned (see [`TagName`]).
+/// * `attributes` - The attributes of the tag.
+pub fn void_tag<'t, A: Attributes>(
+    tag: impl Into<TagName<'t>>,
+    attributes: A,
+) -> VoidTag<'t, A> {
+    VoidTag {
+        tag: tag.into(),
+        attributes,
+    }
+}
+
+/// Information about an HTML element, see [`ELEMENTS`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ElementInfo {
+    /// The name of the element.
+    pub name: &'static str,
+    /// The description of the element, from the WHATWG element index.
+    pub description: &'static str,
+    /// Whether the element is a void element, which can't have content.
+    pub void: bool,
+    /// Whether the element is obsolete, its function is then deprecated.
+    pub obsolete: bool,
+}
+
 // see https://stackoverflow.com/questions/41361897/documenting-a-function-created-with-a-macro-in-rust
 macro_rules! define_tag {
-    ($(#[$attr:meta])* $tag:ident $compactability:expr) => {
-/// Creates a new
-$(#[$attr])*
-/// tag.
+    ($(#[$attr:meta])* $tag:ident $description:literal $compactability:expr) => {
+#[doc = concat!("Creates a new [`<", stringify!($tag), ">`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/", stringify!($tag), ") tag.")]
+///
+#[doc = concat!($description, ".")]
 ///
 /// See [`tag`] for more information.
+$(#[$attr])*
 pub fn $tag(
     attributes: impl Attributes,
     inner_html: impl Html,
@@ -179,655 +388,204 @@ pub fn $tag(
         $compactability,
     )
 }
+
+impl<'a, 't, W: HtmlEnv> InsideTagHtml<'a, 't, W> {
+    #[doc = concat!("Opens a new [`<", stringify!($tag), ">`](", stringify!($tag), "()) tag inside this one.")]
+    ///
+    /// See [`InsideTagHtml::open_tag`] for more information.
+    $(#[$attr])*
+    pub fn $tag(&mut self) -> Result<TagOpening<'_, 'static, Self>, Error> {
+        self.open_tag(stringify!($tag), $compactability)
+    }
+}
     };
 }
 
+macro_rules! define_void_tag {
+    ($(#[$attr:meta])* $tag:ident $description:literal) => {
+#[doc = concat!("Creates a new [`<", stringify!($tag), ">`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/", stringify!($tag), ") tag.")]
+///
+#[doc = concat!($description, ".")]
+///
+/// It is a void element, see [`VoidTag`] for more information.
+/// The `inner_html` argument is always [`Empty`].
+$(#[$attr])*
+pub fn $tag(
+    attributes: impl Attributes,
+    _inner_html: Empty,
+) -> VoidTag<'static, impl Attributes> {
+    void_tag(
+        stringify!($tag),
+        attributes,
+    )
+}
 
+impl<'a, 't, W: HtmlEnv> InsideTagHtml<'a, 't, W> {
+    #[doc = concat!("Opens a new [`<", stringify!($tag), ">`](", stringify!($tag), "()) tag inside this one.")]
+    ///
+    /// It is a void element, so it can only be closed with [`TagOpening::close`].
+    $(#[$attr])*
+    pub fn $tag(&mut self) -> Result<TagOpening<'_, 'static, Self>, Error> {
+        self.open_tag(stringify!($tag), Compactability::Yes { final_slash: false })
+    }
+}
+    };
+}
 
-define_tag!(
-    /// [`<a>`](https://www.w3schools.com/tags/tag_a.asp)
-    a
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<abbr>`](https://www.w3schools.com/tags/tag_abbr.asp)
-    abbr
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<address>`](https://www.w3schools.com/tags/tag_address.asp)
-    address
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<area>`](https://www.w3schools.com/tags/tag_area.asp)
-    area
-    Compactability::Yes { final_slash: false }
-);
-
-define_tag!(
-    /// [`<article>`](https://www.w3schools.com/tags/tag_article.asp)
-    article
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<aside>`](https://www.w3schools.com/tags/tag_aside.asp)
-    aside
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<audio>`](https://www.w3schools.com/tags/tag_audio.asp)
-    audio
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<b>`](https://www.w3schools.com/tags/tag_b.asp)
-    b
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<base>`](https://www.w3schools.com/tags/tag_base.asp)
-    base
-    Compactability::Yes { final_slash: false }
-);
-
-define_tag!(
-    /// [`<bdi>`](https://www.w3schools.com/tags/tag_bdi.asp)
-    bdi
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<bdo>`](https://www.w3schools.com/tags/tag_bdo.asp)
-    bdo
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<blockquote>`](https://www.w3schools.com/tags/tag_blockquote.asp)
-    blockquote
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<body>`](https://www.w3schools.com/tags/tag_body.asp)
-    body
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<br>`](https://www.w3schools.com/tags/tag_br.asp)
-    br
-    Compactability::Yes { final_slash: false }
-);
-
-define_tag!(
-    /// [`<button>`](https://www.w3schools.com/tags/tag_button.asp)
-    button
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<canvas>`](https://www.w3schools.com/tags/tag_canvas.asp)
-    canvas
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<caption>`](https://www.w3schools.com/tags/tag_caption.asp)
-    caption
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<cite>`](https://www.w3schools.com/tags/tag_cite.asp)
-    cite
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<code>`](https://www.w3schools.com/tags/tag_code.asp)
-    code
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<col>`](https://www.w3schools.com/tags/tag_col.asp)
-    col
-    Compactability::Yes { final_slash: false }
-);
-
-define_tag!(
-    /// [`<colgroup>`](https://www.w3schools.com/tags/tag_colgroup.asp)
-    colgroup
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<data>`](https://www.w3schools.com/tags/tag_data.asp)
-    data
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<datalist>`](https://www.w3schools.com/tags/tag_datalist.asp)
-    datalist
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<dd>`](https://www.w3schools.com/tags/tag_dd.asp)
-    dd
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<del>`](https://www.w3schools.com/tags/tag_del.asp)
-    del
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<details>`](https://www.w3schools.com/tags/tag_details.asp)
-    details
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<dfn>`](https://www.w3schools.com/tags/tag_dfn.asp)
-    dfn
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<dialog>`](https://www.w3schools.com/tags/tag_dialog.asp)
-    dialog
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<div>`](https://www.w3schools.com/tags/tag_div.asp)
-    div
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<dl>`](https://www.w3schools.com/tags/tag_dl.asp)
-    dl
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<dt>`](https://www.w3schools.com/tags/tag_dt.asp)
-    dt
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<em>`](https://www.w3schools.com/tags/tag_em.asp)
-    em
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<embed>`](https://www.w3schools.com/tags/tag_embed.asp)
-    embed
-    Compactability::Yes { final_slash: false }
-);
-
-define_tag!(
-    /// [`<fieldset>`](https://www.w3schools.com/tags/tag_fieldset.asp)
-    fieldset
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<figcaption>`](https://www.w3schools.com/tags/tag_figcaption.asp)
-    figcaption
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<figure>`](https://www.w3schools.com/tags/tag_figure.asp)
-    figure
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<footer>`](https://www.w3schools.com/tags/tag_footer.asp)
-    footer
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<form>`](https://www.w3schools.com/tags/tag_form.asp)
-    form
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<h1>`](https://www.w3schools.com/tags/tag_hn.asp)
-    h1
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<h2>`](https://www.w3schools.com/tags/tag_hn.asp)
-    h2
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<h3>`](https://www.w3schools.com/tags/tag_hn.asp)
-    h3
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<h4>`](https://www.w3schools.com/tags/tag_hn.asp)
-    h4
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<h5>`](https://www.w3schools.com/tags/tag_hn.asp)
-    h5
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<h6>`](https://www.w3schools.com/tags/tag_hn.asp)
-    h6
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<head>`](https://www.w3schools.com/tags/tag_head.asp)
-    head
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<header>`](https://www.w3schools.com/tags/tag_header.asp)
-    header
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<hgroup>`](https://www.w3schools.com/tags/tag_hgroup.asp)
-    hgroup
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<hr>`](https://www.w3schools.com/tags/tag_hr.asp)
-    hr
-    Compactability::Yes { final_slash: false }
-);
-
-define_tag!(
-    /// [`<html>`](https://www.w3schools.com/tags/tag_html.asp)
-    html
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<i>`](https://www.w3schools.com/tags/tag_i.asp)
-    i
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<iframe>`](https://www.w3schools.com/tags/tag_iframe.asp)
-    iframe
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<img>`](https://www.w3schools.com/tags/tag_img.asp)
-    img
-    Compactability::Yes { final_slash: false }
-);
-
-define_tag!(
-    /// [`<input>`](https://www.w3schools.com/tags/tag_input.asp)
-    input
-    Compactability::Yes { final_slash: false }
-);
-
-define_tag!(
-    /// [`<ins>`](https://www.w3schools.com/tags/tag_ins.asp)
-    ins
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<kbd>`](https://www.w3schools.com/tags/tag_kbd.asp)
-    kbd
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<label>`](https://www.w3schools.com/tags/tag_label.asp)
-    label
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<legend>`](https://www.w3schools.com/tags/tag_legend.asp)
-    legend
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<li>`](https://www.w3schools.com/tags/tag_li.asp)
-    li
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<link>`](https://www.w3schools.com/tags/tag_link.asp)
-    link
-    Compactability::Yes { final_slash: false }
-);
-
-define_tag!(
-    /// [`<main>`](https://www.w3schools.com/tags/tag_main.asp)
-    main
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<map>`](https://www.w3schools.com/tags/tag_map.asp)
-    map
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<mark>`](https://www.w3schools.com/tags/tag_mark.asp)
-    mark
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<meta>`](https://www.w3schools.com/tags/tag_meta.asp)
-    meta
-    Compactability::Yes { final_slash: false }
-);
-
-define_tag!(
-    /// [`<nav>`](https://www.w3schools.com/tags/tag_nav.asp)
-    nav
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<noscript>`](https://www.w3schools.com/tags/tag_noscript.asp)
-    noscript
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<object>`](https://www.w3schools.com/tags/tag_object.asp)
-    object
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<ol>`](https://www.w3schools.com/tags/tag_ol.asp)
-    ol
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<optgroup>`](https://www.w3schools.com/tags/tag_optgroup.asp)
-    optgroup
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<option>`](https://www.w3schools.com/tags/tag_option.asp)
-    option
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<output>`](https://www.w3schools.com/tags/tag_output.asp)
-    output
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<p>`](https://www.w3schools.com/tags/tag_p.asp)
-    p
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<param>`](https://www.w3schools.com/tags/tag_param.asp)
-    param
-    Compactability::Yes { final_slash: false }
-);
-
-define_tag!(
-    /// [`<pre>`](https://www.w3schools.com/tags/tag_pre.asp)
-    pre
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<progress>`](https://www.w3schools.com/tags/tag_progress.asp)
-    progress
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<q>`](https://www.w3schools.com/tags/tag_q.asp)
-    q
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<rp>`](https://www.w3schools.com/tags/tag_rp.asp)
-    rp
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<rt>`](https://www.w3schools.com/tags/tag_rt.asp)
-    rt
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<ruby>`](https://www.w3schools.com/tags/tag_ruby.asp)
-    ruby
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<s>`](https://www.w3schools.com/tags/tag_s.asp)
-    s
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<samp>`](https://www.w3schools.com/tags/tag_samp.asp)
-    samp
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<script>`](https://www.w3schools.com/tags/tag_script.asp)
-    script
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<section>`](https://www.w3schools.com/tags/tag_section.asp)
-    section
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<select>`](https://www.w3schools.com/tags/tag_select.asp)
-    select
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<small>`](https://www.w3schools.com/tags/tag_small.asp)
-    small
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<source>`](https://www.w3schools.com/tags/tag_source.asp)
-    source
-    Compactability::Yes { final_slash: false }
-);
-
-define_tag!(
-    /// [`<span>`](https://www.w3schools.com/tags/tag_span.asp)
-    span
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<strong>`](https://www.w3schools.com/tags/tag_strong.asp)
-    strong
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<style>`](https://www.w3schools.com/tags/tag_style.asp)
-    style
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<sub>`](https://www.w3schools.com/tags/tag_sub.asp)
-    sub
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<summary>`](https://www.w3schools.com/tags/tag_summary.asp)
-    summary
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<sup>`](https://www.w3schools.com/tags/tag_sup.asp)
-    sup
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<table>`](https://www.w3schools.com/tags/tag_table.asp)
-    table
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<tbody>`](https://www.w3schools.com/tags/tag_tbody.asp)
-    tbody
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<td>`](https://www.w3schools.com/tags/tag_td.asp)
-    td
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<template>`](https://www.w3schools.com/tags/tag_template.asp)
-    template
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<textarea>`](https://www.w3schools.com/tags/tag_textarea.asp)
-    textarea
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<tfoot>`](https://www.w3schools.com/tags/tag_tfoot.asp)
-    tfoot
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<th>`](https://www.w3schools.com/tags/tag_th.asp)
-    th
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<thead>`](https://www.w3schools.com/tags/tag_thead.asp)
-    thead
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<time>`](https://www.w3schools.com/tags/tag_time.asp)
-    time
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<title>`](https://www.w3schools.com/tags/tag_title.asp)
-    title
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<tr>`](https://www.w3schools.com/tags/tag_tr.asp)
-    tr
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<track>`](https://www.w3schools.com/tags/tag_track.asp)
-    track
-    Compactability::Yes { final_slash: false }
-);
-
-define_tag!(
-    /// [`<u>`](https://www.w3schools.com/tags/tag_u.asp)
-    u
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<ul>`](https://www.w3schools.com/tags/tag_ul.asp)
-    ul
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<var>`](https://www.w3schools.com/tags/tag_var.asp)
-    var
-    Compactability::No
-);
-
-define_tag!(
-    /// [`<video>`](https://www.w3schools.com/tags/tag_video.asp)
-    video
-    Compactability::No
-);
+/// Defines the functions of the elements, and [`ELEMENTS`].
+macro_rules! define_tags {
+    ($($(#[$attr:meta])* $kind:ident $tag:ident $description:literal;)*) => {
+        $(
+            define_tags!(@$kind $(#[$attr])* $tag $description);
+        )*
+
+        /// The HTML elements of this module, with a function for each of them.
+        ///
+        /// The list is taken from the [WHATWG element index](https://html.spec.whatwg.org/multipage/indices.html#elements-3),
+        /// with the obsolete elements that used to be supported.
+        pub const ELEMENTS: &[ElementInfo] = &[
+            $(
+                ElementInfo {
+                    name: stringify!($tag),
+                    description: $description,
+                    void: define_tags!(@is_void $kind),
+                    obsolete: define_tags!(@is_obsolete $kind),
+                },
+            )*
+        ];
+    };
+    (@element $(#[$attr:meta])* $tag:ident $description:literal) => {
+        define_tag!($(#[$attr])* $tag $description Compactability::No);
+    };
+    (@void $(#[$attr:meta])* $tag:ident $description:literal) => {
+        define_void_tag!($(#[$attr])* $tag $description);
+    };
+    (@obsolete_void $(#[$attr:meta])* $tag:ident $description:literal) => {
+        define_void_tag!($(#[$attr])* $tag $description);
+    };
+    (@is_void element) => { false };
+    (@is_void $kind:ident) => { true };
+    (@is_obsolete obsolete_void) => { true };
+    (@is_obsolete $kind:ident) => { false };
+}
 
-define_tag!(
-    /// [`<wbr>`](https://www.w3schools.com/tags/tag_wbr.asp)
-    wbr
-    Compactability::Yes { final_slash: false }
-);
\ No newline at end of file
+define_tags! {
+    element a "Hyperlink";
+    element abbr "Abbreviation";
+    element address "Contact information for a page or article element";
+    void area "Hyperlink or dead area on an image map";
+    element article "Self-contained syndicatable or reusable composition";
+    element aside "Sidebar for tangentially related content";
+    element audio "Audio player";
+    element b "Keywords";
+    void base "Base URL and default target navigable for hyperlinks and forms";
+    element bdi "Text directionality isolation";
+    element bdo "Text directionality formatting";
+    element blockquote "A section quoted from another source";
+    element body "Document body";
+    void br "Line break, e.g. in poem or postal address";
+    element button "Button control";
+    element canvas "Scriptable bitmap canvas";
+    element caption "Table caption";
+    element cite "Title of a work";
+    element code "Computer code";
+    void col "Table column";
+    element colgroup "Group of columns in a table";
+    element data "Machine-readable equivalent";
+    element datalist "Container for options for combo box control";
+    element dd "Content for corresponding dt element(s)";
+    element del "A removal from the document";
+    element details "Disclosure control for hiding details";
+    element dfn "Defining instance";
+    element dialog "Dialog box or window";
+    element div "Generic flow container, or container for name-value groups in dl elements";
+    element dl "Association list consisting of zero or more name-value groups";
+    element dt "Legend for corresponding dd element(s)";
+    element em "Stress emphasis";
+    void embed "Plugin";
+    element fieldset "Group of form controls";
+    element figcaption "Caption for figure";
+    element figure "Figure with optional caption";
+    element footer "Footer for a page or section";
+    element form "User-submittable form";
+    element h1 "Heading";
+    element h2 "Heading";
+    element h3 "Heading";
+    element h4 "Heading";
+    element h5 "Heading";
+    element h6 "Heading";
+    element head "Container for document metadata";
+    element header "Introductory or navigational aids for a page or section";
+    element hgroup "Heading container";
+    void hr "Thematic break";
+    element html "Root element";
+    element i "Alternate voice";
+    element iframe "Child navigable";
+    void img "Image";
+    void input "Form control";
+    element ins "An addition to the document";
+    element kbd "User input";
+    element label "Caption for a form control";
+    element legend "Caption for fieldset";
+    element li "List item";
+    void link "Link metadata";
+    element main "Container for the dominant contents of the document";
+    element map "Image map";
+    element mark "Highlight";
+    /// The MathML elements are in the [`math`](mod@math) module.
+    element math "MathML root";
+    element menu "Menu of commands";
+    void meta "Text metadata";
+    element meter "Gauge";
+    element nav "Section with navigational links";
+    element noscript "Fallback content for script";
+    element object "Image, child navigable, or plugin";
+    element ol "Ordered list";
+    element optgroup "Group of options in a list box";
+    element option "Option in a list box or combo box control";
+    element output "Calculated output value";
+    element p "Paragraph";
+    #[deprecated(note = "`param` is obsolete, use the `data` attribute of `object` instead")]
+    obsolete_void param "Parameter for object";
+    element picture "Image";
+    element pre "Block of preformatted text";
+    element progress "Progress bar";
+    element q "Quotation";
+    element rp "Parenthesis for ruby annotation text";
+    element rt "Ruby annotation text";
+    element ruby "Ruby annotation(s)";
+    element s "Inaccurate text";
+    element samp "Computer output";
+    element script "Embedded script";
+    element search "Container for search controls";
+    element section "Generic document or application section";
+    element select "List box control";
+    element slot "Shadow tree slot";
+    element small "Side comment";
+    void source "Image source for img or media source for video or audio";
+    element span "Generic phrasing container";
+    element strong "Importance";
+    element style "Embedded styling information";
+    element sub "Subscript";
+    element summary "Caption for details";
+    element sup "Superscript";
+    /// The SVG elements are in the [`svg`](mod@svg) module.
+    element svg "SVG root";
+    element table "Table";
+    element tbody "Group of rows in a table";
+    element td "Table cell";
+    element template "Template";
+    element textarea "Multiline text controls";
+    element tfoot "Group of footer rows in a table";
+    element th "Table header cell";
+    element thead "Group of heading rows in a table";
+    element time "Machine-readable equivalent of date- or time-related data";
+    element title "Document title";
+    element tr "Table row";
+    void track "Timed text track";
+    element u "Unarticulated annotation";
+    element ul "List";
+    element var "Variable";
+    element video "Video player";
+    void wbr "Line breaking opportunity";
+}
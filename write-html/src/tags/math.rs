@@ -0,0 +1,77 @@
+/*!
+Provides functions for creating MathML tags.
+
+The functions cover the elements of [MathML Core](https://www.w3.org/TR/mathml-core/), which
+are supported by browsers without any script.
+All the elements are written as `<tag/>` when they have no content, which is valid both in
+MathML documents and in MathML embedded in HTML.
+
+To write equations from LaTeX, see [`LatexMath`](crate::LatexMath).
+
+# Example
+```
+use write_html::*;
+
+let half = html!(
+    math {
+        mfrac {
+            mn { 1 }
+            mn { 2 }
+        }
+    }
+).to_html_string().unwrap();
+
+assert_eq!(half, "<math><mfrac><mn>1</mn><mn>2</mn></mfrac></math>");
+```
+*/
+
+use crate::{Attributes, Html, Compactability};
+use super::{tag, Tag};
+
+
+/// The MathML namespace, to be set as `xmlns` attribute of the root `<math>` of a standalone MathML document.
+///
+/// It is not needed for MathML embedded in HTML.
+pub const XMLNS: &str = "http://www.w3.org/1998/Math/MathML";
+
+/// Defines the functions of the elements, and [`ELEMENTS`].
+macro_rules! define_math_tags {
+    ($($tag:ident)* $(; $name:literal $function:ident)*) => {
+        $(
+            define_math_tag!(stringify!($tag), $tag);
+        )*
+        $(
+            define_math_tag!($name, $function);
+        )*
+
+        /// The names of the MathML elements of this module, with a function for each of them.
+        pub const ELEMENTS: &[&str] = &[$(stringify!($tag),)* $($name,)*];
+    };
+}
+
+macro_rules! define_math_tag {
+    ($name:expr, $tag:ident) => {
+#[doc = concat!("Creates a new MathML [`<", $name, ">`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/", $name, ") tag.")]
+///
+/// See [`tag`](super::tag) for more information.
+pub fn $tag(
+    attributes: impl Attributes,
+    inner_html: impl Html,
+) -> Tag<'static, impl Attributes, impl Html, false> {
+    tag(
+        $name,
+        attributes,
+        inner_html,
+        Compactability::Yes { final_slash: true },
+    )
+}
+    };
+}
+
+define_math_tags!(
+    annotation maction math merror mfrac mi mmultiscripts mn mo mover mpadded mphantom
+    mprescripts mroot mrow ms mspace msqrt mstyle msub msubsup msup mtable mtd mtext mtr
+    munder munderover semantics;
+    // names with a hyphen
+    "annotation-xml" annotation_xml
+);
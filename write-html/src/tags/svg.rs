@@ -0,0 +1,118 @@
+/*!
+Provides functions for creating SVG tags.
+
+The names of the functions are the names of the [SVG 2 elements](https://www.w3.org/TR/SVG2/eltindex.html),
+with their case preserved (like [`linearGradient`] or [`foreignObject`]).
+All the elements are written as `<tag/>` when they have no content, which is valid both
+in SVG documents and in SVG embedded in HTML.
+
+# Example
+```
+use write_html::*;
+
+let icon = html!(
+    svg viewBox="0 0 10 10" {
+        defs {
+            linearGradient #grad {
+                stop offset="0" stop-color="red";
+                stop offset="1" stop-color="blue";
+            }
+        }
+        circle cx="5" cy="5" r="4" fill="url(#grad)";
+        use href="#grad";
+    }
+).to_html_string().unwrap();
+
+assert_eq!(icon, concat!(
+    "<svg viewBox=\"0 0 10 10\"><defs><linearGradient id=\"grad\">",
+    "<stop offset=\"0\" stop-color=\"red\"/><stop offset=\"1\" stop-color=\"blue\"/>",
+    "</linearGradient></defs><circle cx=\"5\" cy=\"5\" r=\"4\" fill=\"url(#grad)\"/>",
+    "<use href=\"#grad\"/></svg>",
+));
+```
+
+In the [`html!`](crate::html) macro, the children of `svg` are SVG elements, and the children
+of `foreignObject` are HTML elements again. Elements unknown to [`tags`](super) are written with [`tag`]:
+```
+use write_html::*;
+
+let s = html!(
+    svg {
+        foreignObject width="100" height="50" {
+            p { portal { "HTML" } }
+        }
+        hatch;
+    }
+).to_html_string().unwrap();
+
+assert_eq!(s, concat!(
+    "<svg><foreignObject width=\"100\" height=\"50\"><p><portal>HTML</portal></p></foreignObject>",
+    "<hatch/></svg>",
+));
+```
+*/
+
+#![allow(non_snake_case)]
+
+use crate::{Attributes, Html, Compactability};
+use super::{tag, Tag};
+
+
+/// The SVG namespace, to be set as `xmlns` attribute of the root `<svg>` of a standalone SVG document.
+///
+/// It is not needed for SVG embedded in HTML.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let doc = tags::svg::svg([("xmlns", tags::svg::XMLNS)], Empty).to_html_string().unwrap();
+/// assert_eq!(doc, "<svg xmlns=\"http://www.w3.org/2000/svg\"/>");
+/// ```
+pub const XMLNS: &str = "http://www.w3.org/2000/svg";
+
+/// Defines the functions of the elements, and [`ELEMENTS`].
+macro_rules! define_svg_tags {
+    ($($tag:ident)* $(; $name:literal $function:ident)*) => {
+        $(
+            define_svg_tag!(stringify!($tag), $tag);
+        )*
+        $(
+            define_svg_tag!($name, $function);
+        )*
+
+        /// The names of the SVG elements of this module, with a function for each of them.
+        pub const ELEMENTS: &[&str] = &[$(stringify!($tag),)* $($name,)*];
+    };
+}
+
+macro_rules! define_svg_tag {
+    ($name:expr, $tag:ident) => {
+#[doc = concat!("Creates a new SVG [`<", $name, ">`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/", $name, ") tag.")]
+///
+/// See [`tag`](super::tag) for more information.
+pub fn $tag(
+    attributes: impl Attributes,
+    inner_html: impl Html,
+) -> Tag<'static, impl Attributes, impl Html, false> {
+    tag(
+        $name,
+        attributes,
+        inner_html,
+        Compactability::Yes { final_slash: true },
+    )
+}
+    };
+}
+
+define_svg_tags!(
+    a animate animateMotion animateTransform circle clipPath defs desc ellipse
+    feBlend feColorMatrix feComponentTransfer feComposite feConvolveMatrix feDiffuseLighting
+    feDisplacementMap feDistantLight feDropShadow feFlood feFuncA feFuncB feFuncG feFuncR
+    feGaussianBlur feImage feMerge feMergeNode feMorphology feOffset fePointLight
+    feSpecularLighting feSpotLight feTile feTurbulence filter foreignObject g image line
+    linearGradient marker mask metadata mpath path pattern polygon polyline radialGradient
+    rect script set stop style svg switch symbol text textPath title tspan view;
+    // `use` is a keyword
+    "use" r#use
+);
@@ -0,0 +1,229 @@
+/*!
+Provides functions for writing XML documents, like feeds, sitemaps or SVG files.
+
+XML documents are written with the same tags and the same [`html!`](crate::html) macro as HTML,
+in an environment whose [serialization](crate::HtmlEnv::serialization) is [`Serialization::Xml`]
+(see [`to_string`] or [`SerializationEnv`]).
+In XML:
+* element and attribute names can be any XML name, with a prefix (like `atom:link` or `xml:lang`),
+* empty elements are written as `<tag/>`,
+* text and attribute values are escaped as XML, and newlines are kept,
+* [`Doctype`](crate::Doctype) writes the [XML declaration](DECLARATION).
+
+# Example
+```
+use write_html::*;
+
+let feed = html!(
+    (xml::Declaration)
+    rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" {
+        channel {
+            title { "News & updates" }
+            link { "https://example.com/" }
+            atom:link href="https://example.com/feed.xml" rel="self";
+            description { (xml::cdata("<p>Latest news</p>")) }
+        }
+    }
+);
+
+assert_eq!(xml::to_string(feed).unwrap(), concat!(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+    "<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\"><channel>",
+    "<title>News &amp; updates</title><link>https://example.com/</link>",
+    "<atom:link href=\"https://example.com/feed.xml\" rel=\"self\"/>",
+    "<description><![CDATA[<p>Latest news</p>]]></description>",
+    "</channel></rss>",
+));
+```
+*/
+
+use std::fmt::Display;
+
+use crate::{Attributes, Compactability, Empty, Error, Html, HtmlEnv, Serialization, SerializationEnv, TagName, TagOpening, Text, tags::Tag};
+
+
+/// The XML declaration, written by [`Declaration`].
+pub const DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>";
+
+/// Tells whether the given string is a valid XML name, with an optional prefix.
+///
+/// A valid name is a [`Name`](https://www.w3.org/TR/xml/#NT-Name) of the XML standard
+/// with at most one colon, which separates the prefix from the local name
+/// (see [Namespaces in XML](https://www.w3.org/TR/xml-names/#ns-qualnames)).
+///
+/// # Example
+/// ```
+/// use write_html::xml::is_valid_name;
+///
+/// assert!(is_valid_name("urlset"));
+/// assert!(is_valid_name("pubDate"));
+/// assert!(is_valid_name("atom:link"));
+/// assert!(is_valid_name("_x.y-z"));
+/// assert!(!is_valid_name("1st"));
+/// assert!(!is_valid_name("a:b:c"));
+/// assert!(!is_valid_name(":a"));
+/// assert!(!is_valid_name("a b"));
+/// ```
+pub fn is_valid_name(name: &str) -> bool {
+    match name.split_once(':') {
+        Some((prefix, local_name)) => is_valid_ncname(prefix) && is_valid_ncname(local_name),
+        None => is_valid_ncname(name),
+    }
+}
+
+/// Tells whether `name` is a valid name without colon.
+fn is_valid_ncname(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if is_name_start_char(c) => chars.all(is_name_char),
+        _ => false,
+    }
+}
+
+/// Tells whether `c` is a `NameStartChar` of the XML standard, except the colon.
+fn is_name_start_char(c: char) -> bool {
+    matches!(c,
+        'A'..='Z' | '_' | 'a'..='z' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}' | '\u{F8}'..='\u{2FF}'
+        | '\u{370}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}' | '\u{200C}'..='\u{200D}' | '\u{2070}'..='\u{218F}'
+        | '\u{2C00}'..='\u{2FEF}' | '\u{3001}'..='\u{D7FF}' | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{EFFFF}'
+    )
+}
+
+/// Tells whether `c` is a `NameChar` of the XML standard, except the colon.
+fn is_name_char(c: char) -> bool {
+    is_name_start_char(c)
+        || matches!(c, '-' | '.' | '0'..='9' | '\u{B7}' | '\u{300}'..='\u{36F}' | '\u{203F}'..='\u{2040}')
+}
+
+/// Writes `html` into a new string, as XML.
+///
+/// See the [module documentation](self) for an example.
+pub fn to_string(html: impl Html) -> Result<String, Error> {
+    let mut env = SerializationEnv::new(String::with_capacity(html.size_hint().0), Serialization::Xml);
+    env.write_html(html)?;
+    Ok(env.into_inner())
+}
+
+/// Creates a new XML element.
+///
+/// This is [`tags::tag`](crate::tags::tag) for elements that have no function, written as
+/// `<tag/>` when they have no content.
+///
+/// # Arguments
+/// * `name` - The name of the element, with an optional prefix (see [`is_valid_name`]).
+/// * `attributes` - The attributes of the element.
+/// * `inner_html` - The content of the element.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let url = xml::element("url", Empty, xml::element("loc", Empty, "https://example.com/".as_html_text()));
+/// assert_eq!(xml::to_string(url).unwrap(), "<url><loc>https://example.com/</loc></url>");
+/// ```
+pub fn element<'t, A: Attributes, I: Html>(
+    name: impl Into<TagName<'t>>,
+    attributes: A,
+    inner_html: I,
+) -> Tag<'t, A, I, false> {
+    crate::tags::tag(name, attributes, inner_html, Compactability::Yes { final_slash: true })
+}
+
+/// Writes an element with the given text.
+pub(crate) fn text_element(env: &mut impl HtmlEnv, name: &str, text: impl Display) -> Result<(), Error> {
+    env.write_html(element(name, Empty, Text(text)))?;
+    Ok(())
+}
+
+/// Writes the XML declaration, see [`DECLARATION`].
+///
+/// Unlike [`Doctype`](crate::Doctype), it is written whatever the serialization of the environment.
+pub struct Declaration;
+
+impl Html for Declaration {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (DECLARATION.len(), Some(DECLARATION.len()))
+    }
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        env.write_str(DECLARATION)?;
+        Ok(())
+    }
+}
+
+/// A namespace declaration, written as an `xmlns` attribute.
+///
+/// See [`namespace`] and [`default_namespace`].
+pub struct Namespace<'a> {
+    prefix: Option<&'a str>,
+    uri: &'a str,
+}
+
+/// Declares a namespace with a prefix, written as `xmlns:prefix="uri"`.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let urlset = xml::element("urlset", Sum(
+///     xml::default_namespace("http://www.sitemaps.org/schemas/sitemap/0.9"),
+///     xml::namespace("image", "http://www.google.com/schemas/sitemap-image/1.1"),
+/// ), Empty);
+/// assert_eq!(xml::to_string(urlset).unwrap(), concat!(
+///     "<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\"",
+///     " xmlns:image=\"http://www.google.com/schemas/sitemap-image/1.1\"/>",
+/// ));
+/// ```
+pub fn namespace<'a>(prefix: &'a str, uri: &'a str) -> Namespace<'a> {
+    Namespace { prefix: Some(prefix), uri }
+}
+
+/// Declares the default namespace, written as `xmlns="uri"`.
+///
+/// See [`namespace`] for an example.
+pub fn default_namespace(uri: &str) -> Namespace<'_> {
+    Namespace { prefix: None, uri }
+}
+
+impl<'a> Attributes for Namespace<'a> {
+    fn write_attributes<'b, 't, W: HtmlEnv>(self, w: &mut TagOpening<'b, 't, W>) -> Result<(), Error> {
+        match self.prefix {
+            Some(prefix) => w.attr(format!("xmlns:{}", prefix), self.uri)?,
+            None => w.attr("xmlns", self.uri)?,
+        };
+        Ok(())
+    }
+}
+
+/// A CDATA section, whose text is not escaped.
+///
+/// See [`cdata`].
+pub struct Cdata<T: Display>(pub T);
+
+/// Creates a CDATA section, written as `<![CDATA[text]]>`.
+///
+/// The `]]>` sequences of the text are split across two sections, so that any text can be written.
+///
+/// # Example
+/// ```
+/// use write_html::*;
+///
+/// let content = xml::element("content", Empty, xml::cdata("<p>a]]>b</p>"));
+/// assert_eq!(
+///     xml::to_string(content).unwrap(),
+///     "<content><![CDATA[<p>a]]]]><![CDATA[>b</p>]]></content>"
+/// );
+/// ```
+pub fn cdata<T: Display>(text: T) -> Cdata<T> {
+    Cdata(text)
+}
+
+impl<T: Display> Html for Cdata<T> {
+    fn write_html(self, env: &mut impl HtmlEnv) -> Result<(), Error> {
+        let text = self.0.to_string();
+        env.write_str("<![CDATA[")?;
+        env.write_str(&text.replace("]]>", "]]]]><![CDATA[>"))?;
+        env.write_str("]]>")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+//! Checks the elements of `write_html::tags` against a snapshot of the WHATWG HTML standard,
+//! and against the tags known to the `html!` macro.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use write_html::{is_valid_tag_name, is_void_element, tags::{self, ELEMENTS}};
+use write_html_macro::known_tags;
+
+struct SpecElement<'a> {
+    description: &'a str,
+    void: bool,
+    obsolete: bool,
+}
+
+fn spec_elements() -> BTreeMap<&'static str, SpecElement<'static>> {
+    include_str!("whatwg-elements.txt")
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let mut columns = line.split('\t');
+            let name = columns.next().unwrap();
+            let description = columns.next().unwrap();
+            let flags = columns.next().unwrap();
+            let element = SpecElement {
+                description,
+                void: flags == "void",
+                obsolete: flags == "obsolete",
+            };
+            (name, element)
+        })
+        .collect()
+}
+
+#[test]
+fn elements_match_the_spec() {
+    let spec = spec_elements();
+
+    for element in ELEMENTS {
+        let spec_element = spec.get(element.name)
+            .unwrap_or_else(|| panic!("<{}> is not in the spec", element.name));
+        assert!(is_valid_tag_name(element.name), "<{}> has an invalid name", element.name);
+        assert_eq!(element.obsolete, spec_element.obsolete, "<{}> obsolete", element.name);
+        if !element.obsolete {
+            assert_eq!(element.description, spec_element.description, "<{}> description", element.name);
+            assert_eq!(element.void, spec_element.void, "<{}> void", element.name);
+        }
+    }
+
+    for (name, spec_element) in &spec {
+        if !spec_element.obsolete {
+            assert!(ELEMENTS.iter().any(|e| e.name == *name), "<{}> is missing", name);
+        }
+    }
+}
+
+#[test]
+fn void_elements_match_the_spec() {
+    for (name, spec_element) in spec_elements() {
+        if !spec_element.obsolete {
+            assert_eq!(is_void_element(name), spec_element.void, "<{}> void", name);
+        }
+    }
+}
+
+/// Returns the names of a list of tags known to the `html!` macro.
+fn macro_tags(tags: &[&'static str]) -> BTreeSet<&'static str> {
+    tags.iter().copied().collect()
+}
+
+#[test]
+fn macro_tags_match_the_functions() {
+    let html: BTreeSet<_> = ELEMENTS.iter().map(|e| e.name).collect();
+    assert_eq!(macro_tags(known_tags!(html)), html);
+
+    let void: BTreeSet<_> = ELEMENTS.iter().filter(|e| e.void).map(|e| e.name).collect();
+    assert_eq!(macro_tags(known_tags!(html_void)), void);
+
+    let svg: BTreeSet<_> = tags::svg::ELEMENTS.iter().copied().collect();
+    assert_eq!(macro_tags(known_tags!(svg)), svg);
+
+    // the macro takes the tags as identifiers, which can't have a hyphen
+    let math: BTreeSet<_> = tags::math::ELEMENTS.iter().copied().filter(|name| !name.contains('-')).collect();
+    assert_eq!(macro_tags(known_tags!(math)), math);
+}